@@ -0,0 +1,330 @@
+use std::fmt::Write;
+
+use bevy::prelude::*;
+use bevy_ui_navigation::prelude::{FocusState, Focusable};
+
+use crate::widget::Disabled;
+
+#[derive(SystemLabel)]
+struct UpdateAccessibilityState;
+
+pub(super) struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AccessibilityTree>()
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_focused_state.label(UpdateAccessibilityState),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_disabled_state.label(UpdateAccessibilityState),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                build_accessibility_tree.after(UpdateAccessibilityState),
+            );
+    }
+}
+
+// Mirrors the subset of `accesskit::Role` used by widgets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessibilityRole {
+    Button,
+    TextBox,
+    SpinButton,
+    List,
+    ListItem,
+    Dialog,
+}
+
+#[derive(Component, Clone, Debug)]
+pub struct AccessibilityNode {
+    pub role: AccessibilityRole,
+    pub name: Option<String>,
+    pub value: Option<String>,
+    pub focused: bool,
+    pub disabled: bool,
+    pub expanded: Option<bool>,
+    pub checked: Option<bool>,
+}
+
+impl AccessibilityNode {
+    pub fn new(role: AccessibilityRole) -> Self {
+        Self {
+            role,
+            name: None,
+            value: None,
+            focused: false,
+            disabled: false,
+            expanded: None,
+            checked: None,
+        }
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+}
+
+pub struct AccessibilityTreeNode {
+    pub entity: Entity,
+    pub node: AccessibilityNode,
+    pub children: Vec<Entity>,
+}
+
+// Flattened tree, laid out like an `accesskit::TreeUpdate`: every node is listed once and
+// references its children by id. `Entity::to_bits` can be used as `accesskit::NodeId`.
+#[derive(Default)]
+pub struct AccessibilityTree {
+    pub roots: Vec<Entity>,
+    pub nodes: Vec<AccessibilityTreeNode>,
+    pub focus: Option<Entity>,
+}
+
+impl AccessibilityTree {
+    pub fn get(&self, entity: Entity) -> Option<&AccessibilityTreeNode> {
+        self.nodes.iter().find(|node| node.entity == entity)
+    }
+
+    pub fn dump(&self) -> String {
+        let mut out = String::new();
+        for &root in &self.roots {
+            self.dump_node(root, 0, &mut out);
+        }
+        out
+    }
+
+    fn dump_node(&self, entity: Entity, depth: usize, out: &mut String) {
+        let tree_node = match self.get(entity) {
+            Some(tree_node) => tree_node,
+            None => return,
+        };
+
+        let node = &tree_node.node;
+        let _ = write!(out, "{}{:?}", "  ".repeat(depth), node.role);
+
+        if let Some(name) = &node.name {
+            let _ = write!(out, " \"{name}\"");
+        }
+        if let Some(value) = &node.value {
+            let _ = write!(out, " value=\"{value}\"");
+        }
+        if node.focused {
+            out.push_str(" [focused]");
+        }
+        if node.disabled {
+            out.push_str(" [disabled]");
+        }
+        match node.expanded {
+            Some(true) => out.push_str(" [expanded]"),
+            Some(false) => out.push_str(" [collapsed]"),
+            None => (),
+        }
+        match node.checked {
+            Some(true) => out.push_str(" [checked]"),
+            Some(false) => out.push_str(" [unchecked]"),
+            None => (),
+        }
+        out.push('\n');
+
+        for &child in &tree_node.children {
+            self.dump_node(child, depth + 1, out);
+        }
+    }
+}
+
+fn update_focused_state(mut q: Query<(&Focusable, &mut AccessibilityNode), Changed<Focusable>>) {
+    for (focus, mut node) in &mut q {
+        let focused = focus.state() == FocusState::Focused;
+        if node.focused != focused {
+            node.focused = focused;
+        }
+    }
+}
+
+fn update_disabled_state(
+    mut q: Query<&mut AccessibilityNode>,
+    q_added: Query<Entity, (With<AccessibilityNode>, Added<Disabled>)>,
+    removed: RemovedComponents<Disabled>,
+) {
+    for e in &q_added {
+        if let Ok(mut node) = q.get_mut(e) {
+            node.disabled = true;
+        }
+    }
+
+    for e in removed.iter() {
+        if let Ok(mut node) = q.get_mut(e) {
+            node.disabled = false;
+        }
+    }
+}
+
+fn build_accessibility_tree(
+    q_changed: Query<(), Or<(Changed<AccessibilityNode>, Changed<Children>)>>,
+    removed: RemovedComponents<AccessibilityNode>,
+    q_roots: Query<Entity, Without<Parent>>,
+    q_children: Query<&Children>,
+    q_nodes: Query<&AccessibilityNode>,
+    mut tree: ResMut<AccessibilityTree>,
+) {
+    if q_changed.is_empty() && removed.iter().next().is_none() {
+        return;
+    }
+
+    let mut roots = vec![];
+    let mut nodes = vec![];
+    for entity in &q_roots {
+        collect_nodes(entity, &q_children, &q_nodes, &mut roots, &mut nodes);
+    }
+
+    tree.focus = nodes
+        .iter()
+        .find(|tree_node| tree_node.node.focused)
+        .map(|tree_node| tree_node.entity);
+    tree.roots = roots;
+    tree.nodes = nodes;
+}
+
+// Walks the UI hierarchy and appends to `found` the nearest descendants that have an
+// `AccessibilityNode`. Entities without one are transparent to the accessibility tree.
+fn collect_nodes(
+    entity: Entity,
+    q_children: &Query<&Children>,
+    q_nodes: &Query<&AccessibilityNode>,
+    found: &mut Vec<Entity>,
+    nodes: &mut Vec<AccessibilityTreeNode>,
+) {
+    if let Ok(node) = q_nodes.get(entity) {
+        found.push(entity);
+
+        let mut children = vec![];
+        if let Ok(entity_children) = q_children.get(entity) {
+            for &child in entity_children.iter() {
+                collect_nodes(child, q_children, q_nodes, &mut children, nodes);
+            }
+        }
+
+        nodes.push(AccessibilityTreeNode {
+            entity,
+            node: node.clone(),
+            children,
+        });
+    } else if let Ok(entity_children) = q_children.get(entity) {
+        for &child in entity_children.iter() {
+            collect_nodes(child, q_children, q_nodes, found, nodes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree_node(
+        entity: Entity,
+        node: AccessibilityNode,
+        children: Vec<Entity>,
+    ) -> AccessibilityTreeNode {
+        AccessibilityTreeNode {
+            entity,
+            node,
+            children,
+        }
+    }
+
+    #[test]
+    fn dump_lists_nodes_with_their_state() {
+        let dialog = Entity::from_raw(0);
+        let field = Entity::from_raw(1);
+        let button = Entity::from_raw(2);
+
+        let tree = AccessibilityTree {
+            roots: vec![dialog],
+            nodes: vec![
+                tree_node(
+                    dialog,
+                    AccessibilityNode {
+                        expanded: Some(true),
+                        ..AccessibilityNode::new(AccessibilityRole::Dialog).with_name("Console")
+                    },
+                    vec![field, button],
+                ),
+                tree_node(
+                    field,
+                    AccessibilityNode {
+                        value: Some("help".to_string()),
+                        focused: true,
+                        ..AccessibilityNode::new(AccessibilityRole::TextBox)
+                    },
+                    vec![],
+                ),
+                tree_node(
+                    button,
+                    AccessibilityNode {
+                        disabled: true,
+                        checked: Some(false),
+                        ..AccessibilityNode::new(AccessibilityRole::Button).with_name("Run")
+                    },
+                    vec![],
+                ),
+            ],
+            focus: Some(field),
+        };
+
+        assert_eq!(
+            tree.dump(),
+            "Dialog \"Console\" [expanded]\n  TextBox value=\"help\" [focused]\n  Button \"Run\" \
+             [disabled] [unchecked]\n"
+        );
+    }
+
+    #[test]
+    fn tree_skips_entities_without_nodes() {
+        let mut app = App::new();
+        app.add_plugin(AccessibilityPlugin);
+
+        let item = app
+            .world
+            .spawn()
+            .insert(AccessibilityNode::new(AccessibilityRole::ListItem).with_name("First"))
+            .id();
+        let container = app.world.spawn().push_children(&[item]).id();
+        let list = app
+            .world
+            .spawn()
+            .insert(AccessibilityNode::new(AccessibilityRole::List))
+            .push_children(&[container])
+            .id();
+
+        app.update();
+
+        let tree = app.world.resource::<AccessibilityTree>();
+        assert_eq!(tree.roots, vec![list]);
+        assert_eq!(tree.get(list).unwrap().children, vec![item]);
+        assert_eq!(tree.dump(), "List\n  ListItem \"First\"\n");
+    }
+
+    #[test]
+    fn disabled_state_follows_component() {
+        let mut app = App::new();
+        app.add_plugin(AccessibilityPlugin);
+
+        let button = app
+            .world
+            .spawn()
+            .insert(AccessibilityNode::new(AccessibilityRole::Button))
+            .insert(Disabled)
+            .id();
+
+        app.update();
+        assert!(app.world.get::<AccessibilityNode>(button).unwrap().disabled);
+
+        app.world.entity_mut(button).remove::<Disabled>();
+        app.update();
+        assert!(app.world.get::<AccessibilityNode>(button).unwrap().disabled == false);
+    }
+}
@@ -0,0 +1,320 @@
+use bevy::prelude::*;
+use bevy_ui_navigation::prelude::{FocusState, Focusable};
+
+use crate::{
+    input_text::{InputText, InputTextKeys},
+    item_list::{ItemClicked, ItemList},
+    key_bindings::{
+        WidgetKeyBindings, SUGGESTION_ACCEPT, SUGGESTION_DISMISS, SUGGESTION_NEXT,
+        SUGGESTION_PREVIOUS,
+    },
+    widget::{screen_to_layout, Disabled, ToStringLabel, Widget, WidgetEvent},
+};
+
+const MAX_SUGGESTIONS: usize = 8;
+
+pub(super) struct AutocompletePlugin;
+
+impl Plugin for AutocompletePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SuggestionAccepted>()
+            .add_system(update_suggestions)
+            .add_system(navigate_suggestions.before(InputTextKeys))
+            .add_system(accept_clicked_suggestion)
+            .add_system(update_suggestion_popup)
+            .add_system(despawn_orphan_popups);
+    }
+}
+
+// Produces the suggestions shown for the current text of a field.
+pub trait SuggestionProvider: Send + Sync {
+    fn suggest(&self, text: &str) -> Vec<String>;
+}
+
+impl<F> SuggestionProvider for F
+where
+    F: Fn(&str) -> Vec<String> + Send + Sync,
+{
+    fn suggest(&self, text: &str) -> Vec<String> {
+        self(text)
+    }
+}
+
+// Suggests the entries starting with the text, ignoring case. An entry already typed in full
+// isn't suggested.
+pub struct StaticSuggestions(pub Vec<String>);
+
+impl SuggestionProvider for StaticSuggestions {
+    fn suggest(&self, text: &str) -> Vec<String> {
+        if text.is_empty() {
+            return vec![];
+        }
+
+        let text_lower = text.to_lowercase();
+        self.0
+            .iter()
+            .filter(|entry| {
+                let entry_lower = entry.to_lowercase();
+                entry_lower != text_lower && entry_lower.starts_with(&text_lower)
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+// Shows a list of suggestions under an `InputText` while it's focused. Arrows or Tab move
+// through them and Enter replaces the text with the highlighted one.
+#[derive(Component)]
+pub struct Autocomplete {
+    provider: Box<dyn SuggestionProvider>,
+    pub max_suggestions: usize,
+    suggestions: Vec<String>,
+    highlighted: Option<usize>,
+    // Text the suggestions were produced for.
+    queried_text: Option<String>,
+    // Hidden until the text changes again.
+    dismissed: bool,
+    popup: Option<Entity>,
+}
+
+impl Autocomplete {
+    pub fn new(provider: impl SuggestionProvider + 'static) -> Self {
+        Self {
+            provider: Box::new(provider),
+            max_suggestions: MAX_SUGGESTIONS,
+            suggestions: vec![],
+            highlighted: None,
+            queried_text: None,
+            dismissed: false,
+            popup: None,
+        }
+    }
+
+    pub fn from_list<S: Into<String>>(entries: impl IntoIterator<Item = S>) -> Self {
+        Self::new(StaticSuggestions(
+            entries.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    pub fn with_max_suggestions(mut self, max_suggestions: usize) -> Self {
+        self.max_suggestions = max_suggestions;
+        self
+    }
+
+    pub fn suggestions(&self) -> &[String] {
+        &self.suggestions
+    }
+
+    pub fn highlighted(&self) -> Option<&str> {
+        self.highlighted
+            .and_then(|index| self.suggestions.get(index))
+            .map(String::as_str)
+    }
+
+    pub fn is_shown(&self) -> bool {
+        self.dismissed == false && self.suggestions.is_empty() == false
+    }
+
+    pub fn dismiss(&mut self) {
+        self.dismissed = true;
+    }
+
+    fn highlight_by(&mut self, delta: isize) {
+        let len = self.suggestions.len() as isize;
+        self.highlighted = match self.highlighted {
+            Some(index) => Some((index as isize + delta).rem_euclid(len) as usize),
+            None if delta > 0 => Some(0),
+            None => Some(len as usize - 1),
+        };
+    }
+
+    // Replaces the text of the field with the suggestion at `index`.
+    fn accept(&mut self, index: usize, input_text: &mut InputText) -> Option<String> {
+        let suggestion = self.suggestions.get(index)?.clone();
+
+        input_text.select_all();
+        input_text.insert_str(&suggestion);
+
+        self.queried_text = Some(input_text.text().to_string());
+        self.suggestions.clear();
+        self.highlighted = None;
+
+        Some(suggestion)
+    }
+}
+
+pub struct SuggestionAccepted {
+    pub entity: Entity,
+    pub suggestion: String,
+}
+
+impl WidgetEvent for SuggestionAccepted {
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+}
+
+// Item list showing the suggestions of a field.
+#[derive(Component)]
+struct AutocompletePopup {
+    field: Entity,
+}
+
+fn update_suggestions(
+    mut q: Query<(&InputText, &Focusable, &mut Autocomplete, Option<&Disabled>)>,
+) {
+    for (input_text, focus, mut autocomplete, disabled) in &mut q {
+        if focus.state() != FocusState::Focused || disabled.is_some() {
+            if autocomplete.queried_text.is_some() {
+                autocomplete.queried_text = None;
+                autocomplete.suggestions.clear();
+                autocomplete.highlighted = None;
+            }
+            continue;
+        }
+
+        if autocomplete.queried_text.as_deref() == Some(input_text.text()) {
+            continue;
+        }
+
+        let mut suggestions = autocomplete.provider.suggest(input_text.text());
+        suggestions.truncate(autocomplete.max_suggestions);
+
+        autocomplete.suggestions = suggestions;
+        autocomplete.highlighted = None;
+        autocomplete.queried_text = Some(input_text.text().to_string());
+        autocomplete.dismissed = false;
+    }
+}
+
+// Keys used on the popup are consumed, so the field doesn't also move its caret or submit.
+fn navigate_suggestions(
+    mut q: Query<(Entity, &Focusable, &mut Autocomplete, &mut InputText), Without<Disabled>>,
+    mut input_keycode: ResMut<Input<KeyCode>>,
+    bindings: Res<WidgetKeyBindings>,
+    mut writer: EventWriter<SuggestionAccepted>,
+) {
+    for (entity, focus, mut autocomplete, mut input_text) in &mut q {
+        if focus.state() != FocusState::Focused || autocomplete.is_shown() == false {
+            continue;
+        }
+
+        if bindings.consume(SUGGESTION_NEXT, &mut input_keycode) {
+            autocomplete.highlight_by(1);
+        } else if bindings.consume(SUGGESTION_PREVIOUS, &mut input_keycode) {
+            autocomplete.highlight_by(-1);
+        } else if bindings.consume(SUGGESTION_DISMISS, &mut input_keycode) {
+            autocomplete.dismiss();
+        } else if let Some(index) = autocomplete.highlighted {
+            if bindings.consume(SUGGESTION_ACCEPT, &mut input_keycode) {
+                if let Some(suggestion) = autocomplete.accept(index, &mut input_text) {
+                    writer.send(SuggestionAccepted { entity, suggestion });
+                }
+            }
+        }
+    }
+}
+
+fn accept_clicked_suggestion(
+    mut q: Query<(&mut Autocomplete, &mut InputText), Without<Disabled>>,
+    q_popups: Query<&AutocompletePopup>,
+    mut clicks: EventReader<ItemClicked>,
+    mut writer: EventWriter<SuggestionAccepted>,
+) {
+    for evt in clicks.iter() {
+        let field = match q_popups.get(evt.entity) {
+            Ok(popup) => popup.field,
+            Err(_) => continue,
+        };
+
+        if let Ok((mut autocomplete, mut input_text)) = q.get_mut(field) {
+            if let Some(suggestion) = autocomplete.accept(evt.index, &mut input_text) {
+                writer.send(SuggestionAccepted {
+                    entity: field,
+                    suggestion,
+                });
+            }
+        }
+    }
+}
+
+fn update_suggestion_popup(
+    mut commands: Commands,
+    mut q: Query<(Entity, &mut Autocomplete, &Node, &GlobalTransform)>,
+    mut q_popups: Query<(&mut ItemList, &mut Style), With<AutocompletePopup>>,
+    asset_server: Res<AssetServer>,
+) {
+    for (entity, mut autocomplete, node, transform) in &mut q {
+        if autocomplete.is_shown() == false {
+            if let Some(popup) = autocomplete.popup.take() {
+                commands.entity(popup).despawn_recursive();
+            }
+            continue;
+        }
+
+        // Popup is a root node, so it's drawn above the widgets around the field.
+        let height = ItemList::height_for(autocomplete.suggestions.len());
+        let left = transform.translation().x - node.size.x / 2.0;
+        let bottom = transform.translation().y - node.size.y / 2.0;
+
+        let popup_style = Style {
+            position_type: PositionType::Absolute,
+            position: screen_to_layout(UiRect::new(
+                Val::Px(left),
+                Val::Undefined,
+                Val::Undefined,
+                Val::Px(bottom - height),
+            )),
+            size: Size::new(Val::Px(node.size.x), Val::Px(height)),
+            ..default()
+        };
+
+        let popup = match autocomplete.popup {
+            Some(popup) => popup,
+            None => {
+                // Positioned right away, so it's never laid out among the other roots.
+                let popup = ItemList::build("Suggestions".label(), &mut commands, &asset_server);
+                commands
+                    .entity(popup)
+                    .insert(popup_style)
+                    .insert(AutocompletePopup { field: entity });
+
+                autocomplete.popup = Some(popup);
+                continue;
+            }
+        };
+
+        let (mut item_list, mut style) = match q_popups.get_mut(popup) {
+            Ok(popup) => popup,
+            Err(_) => continue,
+        };
+
+        if item_list.items != autocomplete.suggestions {
+            item_list.items = autocomplete.suggestions.clone();
+        }
+        if item_list.highlighted != autocomplete.highlighted {
+            item_list.highlighted = autocomplete.highlighted;
+        }
+
+        if style.position != popup_style.position || style.size != popup_style.size {
+            style.position = popup_style.position;
+            style.size = popup_style.size;
+        }
+    }
+}
+
+fn despawn_orphan_popups(
+    mut commands: Commands,
+    q: Query<&Autocomplete>,
+    q_popups: Query<(Entity, &AutocompletePopup)>,
+) {
+    for (entity, popup) in &q_popups {
+        let owned = q
+            .get(popup.field)
+            .map_or(false, |autocomplete| autocomplete.popup == Some(entity));
+
+        if owned == false {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -1,20 +1,53 @@
-use bevy::{prelude::*, ui::FocusPolicy};
-use bevy_ui_navigation::prelude::Focusable;
+use std::{collections::HashMap, marker::PhantomData, time::Duration};
 
-use crate::widget::{Widget, WidgetEvent, WidgetLabel};
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::*,
+    text::{DefaultTextPipeline, PositionedGlyph},
+    ui::FocusPolicy,
+};
+use bevy_ui_navigation::{
+    prelude::{FocusState, Focusable},
+    systems::InputMapping,
+};
 
-const NORMAL_COLOR: Color = Color::NONE;
-const HOVERED_COLOR: Color = Color::rgba(0.8, 0.8, 0.8, 0.3);
-const CLICKED_COLOR: Color = Color::rgba(0.05, 0.05, 0.05, 0.5);
+use crate::{
+    accessibility::{AccessibilityNode, AccessibilityRole},
+    key_bindings::{key_code_for_char, KeyChord, WidgetKeyBindings, BUTTON_ACTIVATE},
+    pointer::PointerTarget,
+    theme::WidgetTheme,
+    tooltip::Tooltip,
+    widget::{screen_to_layout, Disabled, Widget, WidgetEvent, WidgetFrames, WidgetLabel},
+};
+
+const HOLD_FILL_COLOR: Color = Color::rgba(0.8, 0.2, 0.2, 0.4);
+
+#[derive(SystemLabel)]
+struct DispatchEvents;
 
 pub(super) struct ButtonPlugin;
 
 impl Plugin for ButtonPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<TextButton>()
+            .register_type::<Toggled>()
+            .register_type::<ButtonGroup>()
+            .register_type::<ButtonLabel>()
             .add_event::<ButtonClicked>()
-            .add_system(dispatch_events)
-            .add_system(update_color);
+            .add_event::<ButtonToggled>()
+            .add_system(dispatch_events.label(DispatchEvents))
+            .add_system(register_shortcut_bindings.before(dispatch_shortcuts))
+            .add_system(dispatch_shortcuts.label(DispatchEvents))
+            .add_system(update_mnemonic_underline)
+            .add_system(update_focus_ring)
+            .add_system(toggle_on_click.after(DispatchEvents))
+            .add_system(update_color)
+            .add_system(update_image_button)
+            .add_system(update_button_icon)
+            .add_system(remove_button_icon)
+            .add_system(update_button_label)
+            .add_system(fit_button_to_text.after(update_button_label))
+            .add_system(update_accessibility_checked);
     }
 }
 
@@ -26,20 +59,322 @@ impl WidgetEvent for ButtonClicked {
     }
 }
 
+pub struct ButtonToggled(pub Entity, pub bool);
+
+impl WidgetEvent for ButtonToggled {
+    fn entity(&self) -> Entity {
+        self.0
+    }
+}
+
+// Makes a button checkable. Clicking it flips the state, unless it's inside a `ButtonGroup`.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Toggled(pub bool);
+
+// Children buttons with `Toggled` are mutually exclusive, like radio buttons.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct ButtonGroup;
+
+// Holding the activate key or the gamepad action button on the focused button presses it like
+// the pointer does, so every trigger applies to keyboard and gamepad activation too.
+#[derive(Component, Clone, Copy, Debug)]
+pub enum ButtonTrigger {
+    // Fires as soon as the button is pressed.
+    Press,
+    // Fires when the button is released while the cursor is still over it.
+    Release,
+    // Fires once the button was held for `duration` seconds.
+    Hold { duration: f32 },
+    // Fires when pressed and then every `interval` seconds after being held for `delay` seconds.
+    Repeat { delay: f32, interval: f32 },
+}
+
+impl Default for ButtonTrigger {
+    fn default() -> Self {
+        ButtonTrigger::Release
+    }
+}
+
 #[derive(Component)]
 struct ButtonMeta {
     mask: Entity,
+    hold_fill: Entity,
+    focus_ring: Entity,
+    pressed: bool,
+    // Set while the button is pressed through the activate key or gamepad button.
+    activated: bool,
+    held_time: f32,
+    next_fire: f32,
+    // Set when the pointer leaves a held button, until it's pressed again.
+    hold_cancelled: bool,
 }
 
-#[derive(Component, Reflect, Default)]
-pub struct ImageButton;
+impl ButtonMeta {
+    fn new(mask: Entity, hold_fill: Entity, focus_ring: Entity) -> Self {
+        Self {
+            mask,
+            hold_fill,
+            focus_ring,
+            pressed: false,
+            activated: false,
+            held_time: 0.0,
+            next_fire: 0.0,
+            hold_cancelled: false,
+        }
+    }
+}
+
+#[derive(Component)]
+struct ButtonHoldFill;
+
+#[derive(Component)]
+struct ButtonFocusRing;
+
+// Sticks out of the button, behind its content, so only the outline is visible.
+fn spawn_focus_ring(commands: &mut Commands) -> Entity {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                position: UiRect::all(Val::Px(-2.0)),
+                ..default()
+            },
+            focus_policy: FocusPolicy::Pass,
+            color: WidgetTheme::default().focus_ring.into(),
+            ..default()
+        })
+        .insert(ButtonFocusRing)
+        .insert(Name::new("Focus Ring"))
+        .id()
+}
+
+fn spawn_hold_fill(commands: &mut Commands) -> Entity {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(0.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                position: UiRect::new(Val::Px(0.0), Val::Undefined, Val::Px(0.0), Val::Undefined),
+                ..default()
+            },
+            focus_policy: FocusPolicy::Pass,
+            color: HOLD_FILL_COLOR.into(),
+            ..default()
+        })
+        .insert(ButtonHoldFill)
+        .insert(Name::new("Hold Fill"))
+        .id()
+}
+
+#[derive(Clone)]
+pub enum ButtonFrame {
+    Image(Handle<Image>),
+    // Cell index on `ImageButton::atlas`, counting from the top-left, row by row.
+    Index(usize),
+}
+
+#[derive(Clone, Default)]
+pub struct ButtonImage {
+    pub frames: Vec<ButtonFrame>,
+    pub fps: f32,
+}
+
+impl ButtonImage {
+    pub fn image(image: Handle<Image>) -> Self {
+        Self {
+            frames: vec![ButtonFrame::Image(image)],
+            fps: 0.0,
+        }
+    }
+
+    pub fn index(index: usize) -> Self {
+        Self {
+            frames: vec![ButtonFrame::Index(index)],
+            fps: 0.0,
+        }
+    }
+
+    pub fn animated(frames: Vec<ButtonFrame>, fps: f32) -> Self {
+        Self { frames, fps }
+    }
+}
+
+// A sprite sheet made of equally sized cells.
+#[derive(Clone)]
+pub struct ButtonAtlas {
+    pub texture: Handle<Image>,
+    pub columns: usize,
+    pub rows: usize,
+}
 
+impl ButtonAtlas {
+    pub fn new(texture: Handle<Image>, columns: usize, rows: usize) -> Self {
+        assert!(
+            columns > 0 && rows > 0,
+            "Button atlas needs at least one cell"
+        );
+        Self {
+            texture,
+            columns,
+            rows,
+        }
+    }
+
+    // UI images can't be cropped, so the whole sheet is scaled to the number of cells and
+    // shifted inside a clipped parent until only the desired cell is visible.
+    fn apply_cell(&self, index: usize, style: &mut Style) {
+        // Fields are public, so an empty atlas is treated as a single cell.
+        let columns = self.columns.max(1);
+        let rows = self.rows.max(1);
+        let column = (index % columns) as f32;
+        let row = (index / columns) as f32;
+
+        style.size = Size::new(
+            Val::Percent(columns as f32 * 100.0),
+            Val::Percent(rows as f32 * 100.0),
+        );
+        style.position = screen_to_layout(UiRect::new(
+            Val::Percent(-column * 100.0),
+            Val::Undefined,
+            Val::Percent(-row * 100.0),
+            Val::Undefined,
+        ));
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ImageButtonState {
+    Normal,
+    Hovered,
+    Pressed,
+    Disabled,
+    Checked,
+}
+
+#[derive(Component, Default)]
+pub struct ImageButton {
+    pub atlas: Option<ButtonAtlas>,
+    pub normal: ButtonImage,
+    pub hovered: Option<ButtonImage>,
+    pub pressed: Option<ButtonImage>,
+    pub disabled: Option<ButtonImage>,
+    pub checked: Option<ButtonImage>,
+}
+
+impl ImageButton {
+    pub fn new(normal: ButtonImage) -> Self {
+        Self {
+            normal,
+            ..default()
+        }
+    }
+
+    pub fn with_atlas(mut self, atlas: ButtonAtlas) -> Self {
+        self.atlas = Some(atlas);
+        self
+    }
+
+    pub fn with_hovered(mut self, image: ButtonImage) -> Self {
+        self.hovered = Some(image);
+        self
+    }
+
+    pub fn with_pressed(mut self, image: ButtonImage) -> Self {
+        self.pressed = Some(image);
+        self
+    }
+
+    pub fn with_disabled(mut self, image: ButtonImage) -> Self {
+        self.disabled = Some(image);
+        self
+    }
+
+    pub fn with_checked(mut self, image: ButtonImage) -> Self {
+        self.checked = Some(image);
+        self
+    }
+
+    // States without an image fall back to the normal one.
+    fn image(&self, state: ImageButtonState) -> &ButtonImage {
+        match state {
+            ImageButtonState::Normal => None,
+            ImageButtonState::Hovered => self.hovered.as_ref(),
+            ImageButtonState::Pressed => self.pressed.as_ref(),
+            ImageButtonState::Disabled => self.disabled.as_ref(),
+            ImageButtonState::Checked => self.checked.as_ref(),
+        }
+        .unwrap_or(&self.normal)
+    }
+}
+
+#[derive(Component)]
+struct ImageButtonMeta {
+    image: Entity,
+    state: Option<ImageButtonState>,
+    frame: usize,
+    frame_timer: Timer,
+}
+
+#[derive(Component)]
+struct ImageButtonImage;
+
+// Shows the default background until other images are given.
 impl Widget for ImageButton {
     fn build<L: WidgetLabel>(
         label: L,
         commands: &mut Commands,
         asset_server: &AssetServer,
     ) -> Entity {
+        let images = ImageButton::new(ButtonImage::image(asset_server.load("ui_bg.png")));
+        ImageButton::build_with_images(label, images, commands)
+    }
+}
+
+impl ImageButton {
+    pub fn build_with_images<L: WidgetLabel>(
+        label: L,
+        images: ImageButton,
+        commands: &mut Commands,
+    ) -> Entity {
+        let image = commands
+            .spawn_bundle(ImageBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    position_type: PositionType::Absolute,
+                    position: UiRect::new(
+                        Val::Px(0.0),
+                        Val::Undefined,
+                        Val::Undefined,
+                        Val::Px(0.0),
+                    ),
+                    ..default()
+                },
+                focus_policy: FocusPolicy::Pass,
+                ..default()
+            })
+            .insert(ImageButtonImage)
+            .insert(Name::new("Image"))
+            .id();
+
+        let clip = commands
+            .spawn_bundle(NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    position_type: PositionType::Absolute,
+                    overflow: Overflow::Hidden,
+                    ..default()
+                },
+                focus_policy: FocusPolicy::Pass,
+                color: Color::NONE.into(),
+                ..default()
+            })
+            .insert(Name::new("Image Clip"))
+            .add_child(image)
+            .id();
+
         let mask = commands
             .spawn_bundle(NodeBundle {
                 style: Style {
@@ -50,13 +385,16 @@ impl Widget for ImageButton {
                     ..default()
                 },
                 focus_policy: FocusPolicy::Pass,
-                color: Color::rgba(0.0, 0.9, 0.0, 0.3).into(),
+                color: Color::NONE.into(),
                 ..default()
             })
             .insert(ButtonMask)
             .insert(Name::new("Mask"))
             .id();
 
+        let hold_fill = spawn_hold_fill(commands);
+        let focus_ring = spawn_focus_ring(commands);
+
         commands
             .spawn_bundle(ButtonBundle {
                 style: Style {
@@ -75,23 +413,193 @@ impl Widget for ImageButton {
                     align_items: AlignItems::Center,
                     ..default()
                 },
-                image: asset_server.load("ui_bg.png").into(),
+                color: Color::NONE.into(),
                 ..default()
             })
             .insert(Name::new(label.name()))
+            .insert(AccessibilityNode::new(AccessibilityRole::Button).with_name(label.name()))
             .insert(label)
             .insert(Focusable::default())
             .insert(Interaction::default())
-            .insert(TextButton)
-            .insert(ButtonMeta { mask })
+            .insert(PointerTarget::default())
+            .insert(images)
+            .insert(ImageButtonMeta {
+                image,
+                state: None,
+                frame: 0,
+                frame_timer: Timer::from_seconds(1.0, true),
+            })
+            .insert(ButtonTrigger::default())
+            .insert(ButtonMeta::new(mask, hold_fill, focus_ring))
+            .add_child(focus_ring)
+            .add_child(clip)
+            .add_child(hold_fill)
             .add_child(mask)
             .id()
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IconPlacement {
+    Before,
+    After,
+    Above,
+    Below,
+}
+
+// Adds an icon to a `TextButton`, next to its text.
+#[derive(Component, Clone)]
+pub struct ButtonIcon {
+    pub image: ButtonFrame,
+    // Required when `image` is a `ButtonFrame::Index`.
+    pub atlas: Option<ButtonAtlas>,
+    pub size: Vec2,
+    pub placement: IconPlacement,
+    pub spacing: f32,
+    pub justify: JustifyContent,
+    // Hides the text, which is then shown as a tooltip.
+    pub icon_only: bool,
+}
+
+impl ButtonIcon {
+    pub fn image(image: Handle<Image>) -> Self {
+        Self {
+            image: ButtonFrame::Image(image),
+            atlas: None,
+            size: Vec2::splat(16.0),
+            placement: IconPlacement::Before,
+            spacing: 4.0,
+            justify: JustifyContent::Center,
+            icon_only: false,
+        }
+    }
+
+    pub fn index(atlas: ButtonAtlas, index: usize) -> Self {
+        Self {
+            image: ButtonFrame::Index(index),
+            atlas: Some(atlas),
+            ..Self::image(Handle::default())
+        }
+    }
+
+    pub fn with_size(mut self, size: Vec2) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn with_placement(mut self, placement: IconPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn with_justify(mut self, justify: JustifyContent) -> Self {
+        self.justify = justify;
+        self
+    }
+
+    pub fn icon_only(mut self) -> Self {
+        self.icon_only = true;
+        self
+    }
+}
+
 #[derive(Component, Reflect, Default)]
 pub struct TextButton;
 
+#[derive(Component)]
+struct TextButtonMeta {
+    content: Entity,
+    text: Entity,
+    icon: Option<Entity>,
+    underline: Option<Entity>,
+}
+
+// Full text of a `TextButton`. Change it to relabel the button, the shown text may be
+// truncated by `ButtonFit`.
+#[derive(Component, Reflect, Default, Clone)]
+#[reflect(Component)]
+pub struct ButtonLabel(pub String);
+
+// Keyboard shortcuts which clicks the button while it's visible and enabled. The accelerator
+// is an action on `WidgetKeyBindings`, bound to the given chord unless the bindings already
+// have it. The mnemonic is the first matching character on the label, underlined and bound
+// to Alt with that key by default.
+#[derive(Component, Clone, Default, Debug)]
+pub struct ButtonShortcut {
+    pub accelerator: Option<(String, KeyChord)>,
+    pub mnemonic: Option<char>,
+}
+
+impl ButtonShortcut {
+    pub fn accelerator(action: impl Into<String>, default_chord: KeyChord) -> Self {
+        Self {
+            accelerator: Some((action.into(), default_chord)),
+            mnemonic: None,
+        }
+    }
+
+    pub fn mnemonic(c: char) -> Self {
+        Self {
+            accelerator: None,
+            mnemonic: Some(c),
+        }
+    }
+
+    pub fn with_mnemonic(mut self, c: char) -> Self {
+        self.mnemonic = Some(c);
+        self
+    }
+
+    fn actions(&self) -> impl Iterator<Item = String> + '_ {
+        let accelerator = self.accelerator.iter().map(|(action, _)| action.clone());
+        accelerator.chain(self.mnemonic.map(mnemonic_action))
+    }
+
+    fn default_bindings(&self) -> impl Iterator<Item = (String, KeyChord)> + '_ {
+        let mnemonic = self.mnemonic.and_then(|c| {
+            let key = key_code_for_char(c)?;
+            Some((mnemonic_action(c), KeyChord::new(key).alt()))
+        });
+
+        self.accelerator.iter().cloned().chain(mnemonic)
+    }
+}
+
+// Mnemonics for the same character share an action, so rebinding it applies to every button.
+pub fn mnemonic_action(c: char) -> String {
+    format!("button.mnemonic.{}", c.to_ascii_lowercase())
+}
+
+#[derive(Component)]
+struct ButtonMnemonicUnderline;
+
+// Sizes a `TextButton` from its measured text instead of a fixed size.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ButtonFit {
+    // Horizontal space added on both sides of the content.
+    pub padding: f32,
+    pub min_width: f32,
+    pub max_width: Option<f32>,
+    // Truncates the text with an ellipsis when it doesn't fit `max_width`.
+    pub ellipsis: bool,
+}
+
+impl Default for ButtonFit {
+    fn default() -> Self {
+        Self {
+            padding: 10.0,
+            min_width: 40.0,
+            max_width: None,
+            ellipsis: true,
+        }
+    }
+}
+
 #[derive(Component)]
 struct ButtonMask;
 
@@ -160,6 +668,9 @@ impl Widget for TextButton {
             .insert(Name::new("Mask"))
             .id();
 
+        let hold_fill = spawn_hold_fill(commands);
+        let focus_ring = spawn_focus_ring(commands);
+
         commands
             .spawn_bundle(ButtonBundle {
                 style: Style {
@@ -181,12 +692,25 @@ impl Widget for TextButton {
                 ..default()
             })
             .insert(Name::new(label.name()))
+            .insert(AccessibilityNode::new(AccessibilityRole::Button).with_name(label.name()))
             .insert(label)
             .insert(Focusable::default())
             .insert(Interaction::default())
+            .insert(PointerTarget::default())
             .insert(TextButton)
-            .insert(ButtonMeta { mask })
+            .insert(ButtonLabel(label.name()))
+            .insert(ButtonTrigger::default())
+            .insert(ButtonMeta::new(mask, hold_fill, focus_ring))
+            .insert(TextButtonMeta {
+                content: bg,
+                text,
+                icon: None,
+                underline: None,
+            })
+            .insert(WidgetFrames(vec![border, bg]))
+            .add_child(focus_ring)
             .add_child(border)
+            .add_child(hold_fill)
             .add_child(mask)
             .id()
     }
@@ -194,27 +718,966 @@ impl Widget for TextButton {
 
 fn update_color(
     mut q_mask: Query<&mut UiColor, With<ButtonMask>>,
-    q: Query<(&ButtonMeta, &Interaction), Changed<Interaction>>,
+    q: Query<
+        (
+            Entity,
+            &ButtonMeta,
+            &Interaction,
+            Option<&Focusable>,
+            Option<&Toggled>,
+            Option<&Disabled>,
+        ),
+        Without<ImageButton>,
+    >,
+    q_changed: Query<
+        Entity,
+        (
+            With<ButtonMeta>,
+            Without<ImageButton>,
+            Or<(
+                Changed<Interaction>,
+                Changed<Focusable>,
+                Changed<Toggled>,
+                Added<Disabled>,
+            )>,
+        ),
+    >,
+    removed: RemovedComponents<Disabled>,
+    theme: Res<WidgetTheme>,
+) {
+    let changed = if theme.is_changed() {
+        q.iter().map(|(e, ..)| e).collect::<Vec<_>>()
+    } else {
+        q_changed.iter().chain(removed.iter()).collect()
+    };
+
+    for e in changed {
+        if let Ok((_, meta, interaction, focus, toggled, disabled)) = q.get(e) {
+            // Some buttons, like the password reveal one, can't be focused.
+            let focused = focus.map_or(false, |focus| focus.state() == FocusState::Focused);
+            if let Ok(mut color) = q_mask.get_mut(meta.mask) {
+                color.0 = if disabled.is_some() {
+                    theme.disabled_mask
+                } else {
+                    match interaction {
+                        Interaction::Clicked => theme.button_clicked,
+                        Interaction::Hovered => theme.button_hovered,
+                        Interaction::None if matches!(toggled, Some(Toggled(true))) => {
+                            theme.button_checked
+                        }
+                        Interaction::None if focused => theme.button_focused,
+                        Interaction::None => theme.button_normal,
+                    }
+                };
+            }
+        }
+    }
+}
+
+fn update_focus_ring(
+    q: Query<(&Focusable, &ButtonMeta, ChangeTrackers<Focusable>)>,
+    mut q_ring: Query<(&mut Style, &mut UiColor), With<ButtonFocusRing>>,
+    theme: Res<WidgetTheme>,
+) {
+    for (focus, meta, focus_tracker) in &q {
+        if focus_tracker.is_changed() == false && theme.is_changed() == false {
+            continue;
+        }
+
+        if let Ok((mut style, mut color)) = q_ring.get_mut(meta.focus_ring) {
+            style.display = if focus.state() == FocusState::Focused {
+                Display::Flex
+            } else {
+                Display::None
+            };
+            color.0 = theme.focus_ring;
+        }
+    }
+}
+
+fn update_image_button(
+    mut q: Query<(
+        Entity,
+        &ImageButton,
+        &Interaction,
+        Option<&Toggled>,
+        Option<&Disabled>,
+        &mut ImageButtonMeta,
+    )>,
+    q_changed: Query<(), Changed<ImageButton>>,
+    mut q_image: Query<(&mut UiImage, &mut UiColor, &mut Style), With<ImageButtonImage>>,
+    time: Res<Time>,
+    theme: Res<WidgetTheme>,
+) {
+    for (e, button, interaction, toggled, disabled, mut meta) in &mut q {
+        let state = if disabled.is_some() {
+            ImageButtonState::Disabled
+        } else {
+            match interaction {
+                Interaction::Clicked => ImageButtonState::Pressed,
+                Interaction::Hovered => ImageButtonState::Hovered,
+                Interaction::None if matches!(toggled, Some(Toggled(true))) => {
+                    ImageButtonState::Checked
+                }
+                Interaction::None => ImageButtonState::Normal,
+            }
+        };
+
+        let image = button.image(state);
+
+        let mut dirty = false;
+        if meta.state != Some(state) || q_changed.contains(e) || theme.is_changed() {
+            meta.state = Some(state);
+            meta.frame = 0;
+            meta.frame_timer.reset();
+            if image.fps > 0.0 {
+                meta.frame_timer
+                    .set_duration(Duration::from_secs_f32(1.0 / image.fps));
+            }
+            dirty = true;
+        } else if image.frames.len() > 1 && image.fps > 0.0 {
+            meta.frame_timer.tick(time.delta());
+            if meta.frame_timer.just_finished() {
+                meta.frame = (meta.frame + 1) % image.frames.len();
+                dirty = true;
+            }
+        }
+
+        if dirty == false {
+            continue;
+        }
+
+        let (mut ui_image, mut color, mut style) = match q_image.get_mut(meta.image) {
+            Ok(image) => image,
+            Err(_) => continue,
+        };
+
+        // Without a dedicated image, disabled buttons are tinted instead.
+        color.0 = if state == ImageButtonState::Disabled && button.disabled.is_none() {
+            theme.disabled_image_tint
+        } else {
+            Color::WHITE
+        };
+
+        match (image.frames.get(meta.frame), &button.atlas) {
+            (Some(ButtonFrame::Image(handle)), _) => {
+                ui_image.0 = handle.clone();
+                style.size = Size::new(Val::Percent(100.0), Val::Percent(100.0));
+                style.position = screen_to_layout(UiRect::new(
+                    Val::Px(0.0),
+                    Val::Undefined,
+                    Val::Px(0.0),
+                    Val::Undefined,
+                ));
+            }
+            (Some(ButtonFrame::Index(index)), Some(atlas)) => {
+                ui_image.0 = atlas.texture.clone();
+                atlas.apply_cell(*index, &mut style);
+            }
+            _ => color.0 = Color::NONE,
+        }
+    }
+}
+
+fn spawn_icon(commands: &mut Commands, icon: &ButtonIcon) -> Entity {
+    let mut image_style = Style {
+        size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+        position_type: PositionType::Absolute,
+        position: UiRect::new(Val::Px(0.0), Val::Undefined, Val::Undefined, Val::Px(0.0)),
+        ..default()
+    };
+
+    let image = match (&icon.image, &icon.atlas) {
+        (ButtonFrame::Image(handle), _) => handle.clone(),
+        (ButtonFrame::Index(index), Some(atlas)) => {
+            atlas.apply_cell(*index, &mut image_style);
+            atlas.texture.clone()
+        }
+        (ButtonFrame::Index(_), None) => {
+            warn!("Button icon with an index needs an atlas");
+            Handle::default()
+        }
+    };
+
+    let image = commands
+        .spawn_bundle(ImageBundle {
+            style: image_style,
+            image: image.into(),
+            focus_policy: FocusPolicy::Pass,
+            ..default()
+        })
+        .id();
+
+    let spacing = Val::Px(if icon.icon_only { 0.0 } else { icon.spacing });
+    let margin = screen_to_layout(match icon.placement {
+        IconPlacement::Before => UiRect::new(Val::Px(0.0), spacing, Val::Px(0.0), Val::Px(0.0)),
+        IconPlacement::After => UiRect::new(spacing, Val::Px(0.0), Val::Px(0.0), Val::Px(0.0)),
+        IconPlacement::Above => UiRect::new(Val::Px(0.0), Val::Px(0.0), Val::Px(0.0), spacing),
+        IconPlacement::Below => UiRect::new(Val::Px(0.0), Val::Px(0.0), spacing, Val::Px(0.0)),
+    });
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Px(icon.size.x), Val::Px(icon.size.y)),
+                flex_shrink: 0.0,
+                margin,
+                overflow: Overflow::Hidden,
+                ..default()
+            },
+            focus_policy: FocusPolicy::Pass,
+            color: Color::NONE.into(),
+            ..default()
+        })
+        .insert(Name::new("Icon"))
+        .add_child(image)
+        .id()
+}
+
+fn update_button_icon(
+    mut commands: Commands,
+    mut q: Query<
+        (
+            Entity,
+            &ButtonIcon,
+            &mut TextButtonMeta,
+            Option<&Tooltip>,
+            &AccessibilityNode,
+        ),
+        Changed<ButtonIcon>,
+    >,
+    mut q_style: Query<&mut Style>,
 ) {
-    for (meta, interaction) in &q {
-        if let Ok(mut color) = q_mask.get_mut(meta.mask) {
-            color.0 = match interaction {
-                Interaction::Clicked => CLICKED_COLOR,
-                Interaction::Hovered => HOVERED_COLOR,
-                Interaction::None => NORMAL_COLOR,
+    for (e, icon, mut meta, tooltip, node) in &mut q {
+        if let Some(old_icon) = meta.icon.take() {
+            commands.entity(old_icon).despawn_recursive();
+        }
+
+        let icon_entity = spawn_icon(&mut commands, icon);
+        commands
+            .entity(meta.content)
+            .insert_children(0, &[icon_entity]);
+        meta.icon = Some(icon_entity);
+
+        if let Ok(mut style) = q_style.get_mut(meta.content) {
+            // Icon is always the first child, so reverse the direction to place it after.
+            style.flex_direction = match icon.placement {
+                IconPlacement::Before => FlexDirection::Row,
+                IconPlacement::After => FlexDirection::RowReverse,
+                // Column grows upwards on screen, so reverse it to keep the icon on top.
+                IconPlacement::Above => FlexDirection::ColumnReverse,
+                IconPlacement::Below => FlexDirection::Column,
             };
+            style.justify_content = icon.justify;
+        }
+
+        if let Ok(mut style) = q_style.get_mut(meta.text) {
+            style.display = if icon.icon_only {
+                Display::None
+            } else {
+                Display::Flex
+            };
+        }
+
+        if icon.icon_only && tooltip.is_none() {
+            if let Some(name) = &node.name {
+                commands.entity(e).insert(Tooltip(name.clone()));
+            }
+        }
+    }
+}
+
+fn remove_button_icon(
+    mut commands: Commands,
+    removed: RemovedComponents<ButtonIcon>,
+    mut q: Query<&mut TextButtonMeta>,
+    mut q_style: Query<&mut Style>,
+) {
+    for e in removed.iter() {
+        if let Ok(mut meta) = q.get_mut(e) {
+            if let Some(icon) = meta.icon.take() {
+                commands.entity(icon).despawn_recursive();
+            }
+
+            if let Ok(mut style) = q_style.get_mut(meta.text) {
+                style.display = Display::Flex;
+            }
+        }
+    }
+}
+
+fn update_button_label(
+    mut q: Query<(&ButtonLabel, &TextButtonMeta, &mut AccessibilityNode), Changed<ButtonLabel>>,
+    mut q_text: Query<&mut Text>,
+) {
+    for (label, meta, mut node) in &mut q {
+        if let Ok(mut text) = q_text.get_mut(meta.text) {
+            if text.sections[0].value != label.0 {
+                text.sections[0].value = label.0.clone();
+            }
+        }
+
+        if node.name.as_ref() != Some(&label.0) {
+            node.name = Some(label.0.clone());
         }
     }
 }
 
+fn fit_button_to_text(
+    mut q: Query<(
+        Entity,
+        &ButtonFit,
+        &ButtonLabel,
+        &TextButtonMeta,
+        Option<&ButtonIcon>,
+        &mut Style,
+    )>,
+    q_changed: Query<
+        (),
+        Or<(
+            Changed<ButtonFit>,
+            Changed<ButtonIcon>,
+            Changed<ButtonLabel>,
+        )>,
+    >,
+    mut q_text: Query<(&CalculatedSize, &mut Text)>,
+    text_pipeline: Res<DefaultTextPipeline>,
+    windows: Res<Windows>,
+) {
+    let scale_factor = windows
+        .get_primary()
+        .map_or(1.0, |window| window.scale_factor() as f32);
+
+    for (e, fit, label, meta, icon, mut style) in &mut q {
+        let (text_size, mut text) = match q_text.get_mut(meta.text) {
+            Ok(text) => text,
+            Err(_) => continue,
+        };
+
+        // Start over from the full label, since the available space may have grown.
+        if q_changed.contains(e) && text.sections[0].value != label.0 {
+            text.sections[0].value = label.0.clone();
+            continue;
+        }
+
+        let text_width = text_size.size.width;
+        let (content_width, icon_width) = match icon {
+            Some(icon) if icon.icon_only => (icon.size.x, 0.0),
+            Some(icon) if matches!(icon.placement, IconPlacement::Above | IconPlacement::Below) => {
+                (text_width.max(icon.size.x), 0.0)
+            }
+            Some(icon) => (
+                text_width + icon.size.x + icon.spacing,
+                icon.size.x + icon.spacing,
+            ),
+            None => (text_width, 0.0),
+        };
+
+        let needed = content_width + fit.padding * 2.0;
+        let width = match fit.max_width {
+            Some(max_width) => needed.clamp(fit.min_width, max_width.max(fit.min_width)),
+            None => needed.max(fit.min_width),
+        };
+
+        if style.size.width != Val::Px(width) {
+            style.size.width = Val::Px(width);
+        }
+
+        let hidden = matches!(icon, Some(icon) if icon.icon_only);
+        if fit.ellipsis == false || hidden || needed <= width + 0.5 {
+            continue;
+        }
+
+        let available = width - fit.padding * 2.0 - icon_width;
+        if let Some((truncated, truncated_width)) = truncate_text(
+            &text.sections[0],
+            text_pipeline
+                .get_glyphs(&meta.text)
+                .map(|layout| &layout.glyphs[..]),
+            available,
+            scale_factor,
+        ) {
+            text.sections[0].value = truncated;
+
+            // Fit the truncated text right away, instead of shrinking on the next frame.
+            let width =
+                Val::Px((truncated_width + icon_width + fit.padding * 2.0).max(fit.min_width));
+            if style.size.width != width {
+                style.size.width = width;
+            }
+        }
+    }
+}
+
+const ELLIPSIS: char = '…';
+
+// Cuts the text on the first glyph which doesn't fit on `available` width, leaving room for the
+// ellipsis, and returns it with its width. Glyph positions are in physical pixels, relative to
+// the left of the text node.
+fn truncate_text(
+    section: &TextSection,
+    glyphs: Option<&[PositionedGlyph]>,
+    available: f32,
+    scale_factor: f32,
+) -> Option<(String, f32)> {
+    let glyphs = glyphs?;
+    let value = section.value.trim_end_matches(ELLIPSIS);
+
+    // Reuse the ellipsis glyph width when it's already there, or make a guess otherwise.
+    let ellipsis_width = match (section.value.ends_with(ELLIPSIS), glyphs.last()) {
+        (true, Some(glyph)) => glyph.size.x / scale_factor,
+        _ => section.style.font_size * 0.8,
+    };
+
+    let cut = glyphs
+        .iter()
+        .filter(|glyph| glyph.byte_index < value.len())
+        .find(|glyph| {
+            (glyph.position.x + glyph.size.x / 2.0) / scale_factor > available - ellipsis_width
+        })
+        .map(|glyph| glyph.byte_index)?;
+
+    let kept = value[..cut].trim_end();
+    let kept_width = glyphs
+        .iter()
+        .filter(|glyph| glyph.byte_index < kept.len())
+        .map(|glyph| (glyph.position.x + glyph.size.x / 2.0) / scale_factor)
+        .fold(0.0, f32::max);
+
+    let truncated = format!("{kept}{ELLIPSIS}");
+    (truncated != section.value).then_some((truncated, kept_width + ellipsis_width))
+}
+
+fn register_shortcut_bindings(
+    q: Query<&ButtonShortcut, Changed<ButtonShortcut>>,
+    mut bindings: ResMut<WidgetKeyBindings>,
+) {
+    for shortcut in &q {
+        for (action, chord) in shortcut.default_bindings() {
+            bindings.bind_default(action, vec![chord]);
+        }
+    }
+}
+
+fn dispatch_shortcuts(
+    q: Query<(Entity, &ButtonShortcut, &ComputedVisibility), Without<Disabled>>,
+    q_parent: Query<&Parent>,
+    input: Res<Input<KeyCode>>,
+    bindings: Res<WidgetKeyBindings>,
+    mut writer: EventWriter<ButtonClicked>,
+    mut last_conflicts: Local<Vec<(KeyChord, Entity)>>,
+) {
+    // Buttons under different roots belong to different screens, so they can share chords.
+    let mut shortcuts = HashMap::<(KeyChord, Entity), Vec<Entity>>::new();
+    for (e, shortcut, visibility) in &q {
+        if visibility.is_visible() == false {
+            continue;
+        }
+
+        let root = root_of(e, &q_parent);
+        for action in shortcut.actions() {
+            for &chord in bindings.chords(&action) {
+                let buttons = shortcuts.entry((chord, root)).or_default();
+                if buttons.contains(&e) == false {
+                    buttons.push(e);
+                }
+            }
+        }
+    }
+
+    let mut conflicts = shortcuts
+        .iter()
+        .filter(|(_, buttons)| buttons.len() > 1)
+        .map(|(&key, _)| key)
+        .collect::<Vec<_>>();
+    conflicts.sort_by_key(|(chord, root)| (chord.to_string(), *root));
+
+    // Only warn when conflicts change, instead of every frame.
+    if *last_conflicts != conflicts {
+        for key in &conflicts {
+            warn!(
+                "Shortcut {} is bound to {} visible buttons under {:?}, ignoring it",
+                key.0,
+                shortcuts[key].len(),
+                key.1
+            );
+        }
+        *last_conflicts = conflicts;
+    }
+
+    for ((chord, _), buttons) in &shortcuts {
+        if buttons.len() == 1 && chord.just_pressed(&input) {
+            writer.send(ButtonClicked(buttons[0]));
+        }
+    }
+}
+
+fn root_of(mut entity: Entity, q_parent: &Query<&Parent>) -> Entity {
+    while let Ok(parent) = q_parent.get(entity) {
+        entity = parent.get();
+    }
+    entity
+}
+
+fn update_mnemonic_underline(
+    mut commands: Commands,
+    mut q: Query<(Entity, &ButtonShortcut, &mut TextButtonMeta)>,
+    q_text: Query<(&Text, ChangeTrackers<CalculatedSize>)>,
+    q_shortcut_changed: Query<(), Changed<ButtonShortcut>>,
+    mut q_underline: Query<&mut Style, With<ButtonMnemonicUnderline>>,
+    text_pipeline: Res<DefaultTextPipeline>,
+    windows: Res<Windows>,
+) {
+    let scale_factor = windows
+        .get_primary()
+        .map_or(1.0, |window| window.scale_factor() as f32);
+
+    for (e, shortcut, mut meta) in &mut q {
+        let (text, size_tracker) = match q_text.get(meta.text) {
+            Ok(text) => text,
+            Err(_) => continue,
+        };
+
+        if size_tracker.is_changed() == false && q_shortcut_changed.contains(e) == false {
+            continue;
+        }
+
+        let value = &text.sections[0].value;
+        let glyph = shortcut
+            .mnemonic
+            .and_then(|mnemonic| {
+                value
+                    .char_indices()
+                    .find(|(_, c)| c.eq_ignore_ascii_case(&mnemonic))
+            })
+            .and_then(|(byte_index, _)| {
+                text_pipeline
+                    .get_glyphs(&meta.text)?
+                    .glyphs
+                    .iter()
+                    .find(|glyph| glyph.byte_index == byte_index)
+            });
+
+        let glyph = match glyph {
+            Some(glyph) => glyph,
+            None => {
+                if let Some(underline) = meta.underline.take() {
+                    commands.entity(underline).despawn_recursive();
+                }
+                continue;
+            }
+        };
+
+        let width = glyph.size.x / scale_factor;
+        let left = glyph.position.x / scale_factor - width / 2.0;
+
+        if let Some(mut style) = meta
+            .underline
+            .and_then(|underline| q_underline.get_mut(underline).ok())
+        {
+            style.size.width = Val::Px(width);
+            style.position.left = Val::Px(left);
+            continue;
+        }
+
+        let underline = commands
+            .spawn_bundle(NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Px(width), Val::Px(1.0)),
+                    position_type: PositionType::Absolute,
+                    position: screen_to_layout(UiRect::new(
+                        Val::Px(left),
+                        Val::Undefined,
+                        Val::Undefined,
+                        Val::Px(0.0),
+                    )),
+                    ..default()
+                },
+                focus_policy: FocusPolicy::Pass,
+                color: text.sections[0].style.color.into(),
+                ..default()
+            })
+            .insert(ButtonMnemonicUnderline)
+            .insert(Name::new("Mnemonic"))
+            .id();
+
+        commands.entity(meta.text).add_child(underline);
+        meta.underline = Some(underline);
+    }
+}
+
+fn update_accessibility_checked(
+    mut q: Query<(&Toggled, &mut AccessibilityNode), Changed<Toggled>>,
+) {
+    for (toggled, mut node) in &mut q {
+        node.checked = Some(toggled.0);
+    }
+}
+
+fn toggle_on_click(
+    mut reader: EventReader<ButtonClicked>,
+    mut q_toggled: Query<&mut Toggled>,
+    q_parent: Query<&Parent>,
+    q_group: Query<&Children, With<ButtonGroup>>,
+    mut writer: EventWriter<ButtonToggled>,
+) {
+    for &ButtonClicked(e) in reader.iter() {
+        let group = q_parent
+            .get(e)
+            .ok()
+            .and_then(|parent| q_group.get(parent.get()).ok());
+
+        let mut toggled = match q_toggled.get_mut(e) {
+            Ok(toggled) => toggled,
+            Err(_) => continue,
+        };
+
+        if let Some(siblings) = group {
+            if toggled.0 {
+                // A group always keeps one button checked.
+                continue;
+            }
+
+            toggled.0 = true;
+            writer.send(ButtonToggled(e, true));
+
+            for &sibling in siblings.iter().filter(|&&sibling| sibling != e) {
+                if let Ok(mut sibling_toggled) = q_toggled.get_mut(sibling)
+                    && sibling_toggled.0
+                {
+                    sibling_toggled.0 = false;
+                    writer.send(ButtonToggled(sibling, false));
+                }
+            }
+        } else {
+            toggled.0 = !toggled.0;
+            writer.send(ButtonToggled(e, toggled.0));
+        }
+    }
+}
+
+// Navigation also sends `NavRequest::Action` when the pointer is released over the focused
+// button, so its requests aren't used for activation, or every click would fire twice.
+#[derive(SystemParam)]
+struct ActivationInput<'w, 's> {
+    keys: Res<'w, Input<KeyCode>>,
+    gamepad_buttons: Res<'w, Input<GamepadButton>>,
+    bindings: Res<'w, WidgetKeyBindings>,
+    mapping: Res<'w, InputMapping>,
+    #[system_param(ignore)]
+    _data: PhantomData<&'s ()>,
+}
+
+impl<'w, 's> ActivationInput<'w, 's> {
+    fn is_action(&self, button: &GamepadButton) -> bool {
+        button.button_type == self.mapping.action_button
+    }
+
+    fn just_pressed(&self) -> bool {
+        self.bindings.just_pressed(BUTTON_ACTIVATE, &self.keys)
+            || self.keys.just_pressed(self.mapping.key_action)
+            || self
+                .gamepad_buttons
+                .get_just_pressed()
+                .any(|button| self.is_action(button))
+    }
+
+    fn pressed(&self) -> bool {
+        self.bindings.pressed(BUTTON_ACTIVATE, &self.keys)
+            || self.keys.pressed(self.mapping.key_action)
+            || self
+                .gamepad_buttons
+                .get_pressed()
+                .any(|button| self.is_action(button))
+    }
+}
+
 fn dispatch_events(
-    q: Query<(Entity, &Interaction), (With<TextButton>, Changed<Interaction>)>,
+    mut q: Query<
+        (
+            Entity,
+            &ButtonTrigger,
+            &PointerTarget,
+            Option<&Focusable>,
+            &mut ButtonMeta,
+        ),
+        Without<Disabled>,
+    >,
+    mut q_fill: Query<&mut Style, With<ButtonHoldFill>>,
     mut writer: EventWriter<ButtonClicked>,
+    activation: ActivationInput,
+    time: Res<Time>,
 ) {
-    for (e, interaction) in &q {
-        match interaction {
-            Interaction::Clicked => writer.send(ButtonClicked(e)),
-            _ => continue,
+    let activate_just_pressed = activation.just_pressed();
+    let activate_pressed = activation.pressed();
+
+    for (e, trigger, pointer, focus, mut meta) in &mut q {
+        let focused = focus.map_or(false, |focus| focus.state() == FocusState::Focused);
+        if focused && activate_just_pressed {
+            meta.activated = true;
+        }
+
+        // Moving the focus away while holding the key is like leaving the button with the
+        // pointer, so releasing the key only fires while the button is still focused.
+        let activation_ended = meta.activated && (activate_pressed == false || focused == false);
+        if activation_ended {
+            meta.activated = false;
+        }
+
+        let is_pressed = pointer.is_pressed() || meta.activated;
+        let hovered = if meta.activated || activation_ended {
+            focused
+        } else {
+            pointer.is_hovered()
+        };
+
+        let just_pressed = is_pressed && meta.pressed == false;
+        let just_released = is_pressed == false && meta.pressed;
+
+        if just_pressed || just_released {
+            meta.pressed = is_pressed;
+            meta.held_time = 0.0;
+        } else if meta.pressed {
+            meta.held_time += time.delta_seconds();
+        }
+
+        match *trigger {
+            ButtonTrigger::Press => {
+                if just_pressed {
+                    writer.send(ButtonClicked(e));
+                }
+            }
+            ButtonTrigger::Release => {
+                if just_released && hovered {
+                    writer.send(ButtonClicked(e));
+                }
+            }
+            ButtonTrigger::Hold { duration } => {
+                if just_pressed {
+                    meta.next_fire = duration;
+                    meta.hold_cancelled = false;
+                }
+
+                // Leaving the button cancels the hold, like releasing it outside.
+                if meta.pressed && hovered == false && meta.hold_cancelled == false {
+                    meta.hold_cancelled = true;
+                }
+
+                let holding = meta.pressed && meta.hold_cancelled == false;
+
+                if holding && meta.held_time >= meta.next_fire {
+                    // Only fire once per press.
+                    meta.next_fire = f32::INFINITY;
+                    writer.send(ButtonClicked(e));
+                }
+
+                let progress = if holding {
+                    (meta.held_time / duration.max(f32::EPSILON)).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+
+                if let Ok(mut style) = q_fill.get_mut(meta.hold_fill) {
+                    let width = Val::Percent(progress * 100.0);
+                    if style.size.width != width {
+                        style.size.width = width;
+                    }
+                }
+            }
+            ButtonTrigger::Repeat { delay, interval } => {
+                if just_pressed {
+                    meta.next_fire = delay;
+                    writer.send(ButtonClicked(e));
+                }
+
+                // Keep repeating only while the cursor is over the button.
+                if meta.pressed && hovered && meta.held_time >= meta.next_fire {
+                    meta.next_fire = meta.held_time + interval;
+                    writer.send(ButtonClicked(e));
+                }
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        asset::AssetPlugin,
+        core::CorePlugin,
+        ecs::{event::Events, system::CommandQueue},
+        input::{keyboard::KeyboardInput, ButtonState, InputPlugin},
+    };
+    use bevy_ui_navigation::{prelude::NavRequest, DefaultNavigationPlugins};
+
+    use super::*;
+    use crate::{pointer::PointerPlugin, widget::StringLabel};
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_plugin(InputPlugin)
+            .add_plugins(DefaultNavigationPlugins)
+            .add_asset::<Font>()
+            .init_resource::<Time>()
+            .init_resource::<Windows>()
+            .init_resource::<DefaultTextPipeline>()
+            .init_resource::<WidgetTheme>()
+            .init_resource::<WidgetKeyBindings>()
+            .add_plugin(PointerPlugin)
+            .add_plugin(ButtonPlugin);
+        app
+    }
+
+    fn spawn_button(app: &mut App) -> Entity {
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &app.world);
+        let button = TextButton::build(StringLabel::from("Button"), &mut commands, &asset_server);
+        queue.apply(&mut app.world);
+
+        // There is no layout, so give the button a size for the pointer to be over it.
+        app.world.get_mut::<Node>(button).unwrap().size = Vec2::new(100.0, 40.0);
+        button
+    }
+
+    // Runs a frame `seconds` after the last one and returns the buttons clicked during it.
+    fn update(app: &mut App, seconds: f32) -> Vec<Entity> {
+        let mut time = app.world.resource_mut::<Time>();
+        let last = time.last_update().unwrap_or_else(|| time.startup());
+        time.update_with_instant(last + Duration::from_secs_f32(seconds));
+
+        app.update();
+        app.world
+            .resource_mut::<Events<ButtonClicked>>()
+            .drain()
+            .map(|ButtonClicked(e)| e)
+            .collect()
+    }
+
+    fn focus(app: &mut App, button: Entity) {
+        app.world
+            .resource_mut::<Events<NavRequest>>()
+            .send(NavRequest::FocusOn(button));
+        update(app, 0.0);
+    }
+
+    fn set_interaction(app: &mut App, button: Entity, interaction: Interaction) {
+        *app.world.get_mut::<Interaction>(button).unwrap() = interaction;
+    }
+
+    // Like the mouse input of navigation, releasing the pointer over the focused button also
+    // requests its action.
+    fn click(app: &mut App, button: Entity) -> Vec<Entity> {
+        set_interaction(app, button, Interaction::Clicked);
+        let mut clicked = update(app, 0.05);
+
+        set_interaction(app, button, Interaction::Hovered);
+        app.world
+            .resource_mut::<Events<NavRequest>>()
+            .send(NavRequest::Action);
+        clicked.extend(update(app, 0.05));
+        clicked.extend(update(app, 0.0));
+        clicked
+    }
+
+    fn send_key(app: &mut App, key_code: KeyCode, state: ButtonState) {
+        app.world
+            .resource_mut::<Events<KeyboardInput>>()
+            .send(KeyboardInput {
+                scan_code: 0,
+                key_code: Some(key_code),
+                state,
+            });
+    }
+
+    #[test]
+    fn click_fires_once() {
+        let mut app = app();
+        let button = spawn_button(&mut app);
+        focus(&mut app, button);
+
+        assert_eq!(click(&mut app, button), vec![button]);
+    }
+
+    #[test]
+    fn activate_key_clicks_focused_button() {
+        let mut app = app();
+        let button = spawn_button(&mut app);
+        // Only the focused button is activated.
+        spawn_button(&mut app);
+        focus(&mut app, button);
+
+        // Like the pointer, the default trigger fires when the key is released.
+        send_key(&mut app, KeyCode::Return, ButtonState::Pressed);
+        assert!(update(&mut app, 0.05).is_empty());
+
+        send_key(&mut app, KeyCode::Return, ButtonState::Released);
+        assert_eq!(update(&mut app, 0.05), vec![button]);
+    }
+
+    #[test]
+    fn click_flips_toggle_once() {
+        let mut app = app();
+        let button = spawn_button(&mut app);
+        app.world.entity_mut(button).insert(Toggled(false));
+        focus(&mut app, button);
+
+        click(&mut app, button);
+        assert!(app.world.get::<Toggled>(button).unwrap().0);
+
+        let toggled = app
+            .world
+            .resource_mut::<Events<ButtonToggled>>()
+            .drain()
+            .map(|ButtonToggled(e, checked)| (e, checked))
+            .collect::<Vec<_>>();
+        assert_eq!(toggled, vec![(button, true)]);
+    }
+
+    fn spawn_hold_button(app: &mut App) -> Entity {
+        let button = spawn_button(app);
+        app.world
+            .entity_mut(button)
+            .insert(ButtonTrigger::Hold { duration: 1.0 });
+        focus(app, button);
+        button
+    }
+
+    #[test]
+    fn short_click_doesnt_fire_hold() {
+        let mut app = app();
+        let button = spawn_hold_button(&mut app);
+
+        assert!(click(&mut app, button).is_empty());
+    }
+
+    #[test]
+    fn short_key_press_doesnt_fire_hold() {
+        let mut app = app();
+        spawn_hold_button(&mut app);
+
+        send_key(&mut app, KeyCode::Return, ButtonState::Pressed);
+        assert!(update(&mut app, 0.1).is_empty());
+        assert!(update(&mut app, 0.5).is_empty());
+
+        send_key(&mut app, KeyCode::Return, ButtonState::Released);
+        assert!(update(&mut app, 0.1).is_empty());
+    }
+
+    #[test]
+    fn holding_key_fires_hold_once() {
+        let mut app = app();
+        let button = spawn_hold_button(&mut app);
+
+        send_key(&mut app, KeyCode::Return, ButtonState::Pressed);
+        assert!(update(&mut app, 0.0).is_empty());
+        assert!(update(&mut app, 0.6).is_empty());
+        assert_eq!(update(&mut app, 0.6), vec![button]);
+        assert!(update(&mut app, 0.6).is_empty());
+
+        send_key(&mut app, KeyCode::Return, ButtonState::Released);
+        assert!(update(&mut app, 0.1).is_empty());
+    }
+}
@@ -0,0 +1,105 @@
+// Text storage used by widgets for copy, cut and paste. The in-memory backend is used by
+// default, enable the `system-clipboard` feature to share text with other applications.
+pub trait Clipboard: Send + Sync {
+    fn get(&mut self) -> Option<String>;
+    fn set(&mut self, text: String);
+}
+
+#[derive(Default)]
+pub struct MemoryClipboard {
+    text: Option<String>,
+}
+
+impl Clipboard for MemoryClipboard {
+    fn get(&mut self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn set(&mut self, text: String) {
+        self.text = Some(text);
+    }
+}
+
+#[cfg(feature = "system-clipboard")]
+pub struct SystemClipboard {
+    // Not every platform backend is `Sync`.
+    inner: std::sync::Mutex<arboard::Clipboard>,
+}
+
+#[cfg(feature = "system-clipboard")]
+impl SystemClipboard {
+    pub fn new() -> Result<Self, arboard::Error> {
+        Ok(Self {
+            inner: std::sync::Mutex::new(arboard::Clipboard::new()?),
+        })
+    }
+}
+
+#[cfg(feature = "system-clipboard")]
+impl Clipboard for SystemClipboard {
+    fn get(&mut self) -> Option<String> {
+        self.inner.get_mut().ok()?.get_text().ok()
+    }
+
+    fn set(&mut self, text: String) {
+        if let Ok(clipboard) = self.inner.get_mut() {
+            if let Err(err) = clipboard.set_text(text) {
+                bevy::log::warn!("Failed to write to the system clipboard: {err}");
+            }
+        }
+    }
+}
+
+pub struct WidgetClipboard(pub Box<dyn Clipboard>);
+
+impl Default for WidgetClipboard {
+    fn default() -> Self {
+        #[cfg(feature = "system-clipboard")]
+        match SystemClipboard::new() {
+            Ok(clipboard) => return Self(Box::new(clipboard)),
+            Err(err) => bevy::log::warn!("System clipboard unavailable, using memory: {err}"),
+        }
+
+        Self(Box::new(MemoryClipboard::default()))
+    }
+}
+
+impl WidgetClipboard {
+    pub fn get(&mut self) -> Option<String> {
+        self.0.get()
+    }
+
+    pub fn set(&mut self, text: impl Into<String>) {
+        self.0.set(text.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_clipboard_starts_empty() {
+        let mut clipboard = MemoryClipboard::default();
+        assert_eq!(clipboard.get(), None);
+    }
+
+    #[test]
+    fn memory_clipboard_keeps_last_text() {
+        let mut clipboard = MemoryClipboard::default();
+        clipboard.set("first".to_string());
+        clipboard.set("second".to_string());
+
+        assert_eq!(clipboard.get().as_deref(), Some("second"));
+        // Reading doesn't take the text out.
+        assert_eq!(clipboard.get().as_deref(), Some("second"));
+    }
+
+    #[test]
+    fn widget_clipboard_forwards_to_backend() {
+        let mut clipboard = WidgetClipboard(Box::new(MemoryClipboard::default()));
+        clipboard.set("copied");
+
+        assert_eq!(clipboard.get().as_deref(), Some("copied"));
+    }
+}
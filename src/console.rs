@@ -2,9 +2,11 @@ use bevy::{prelude::*, ui::FocusPolicy};
 use bevy_ui_navigation::prelude::NavRequest;
 
 use crate::{
-    input_text::InputText,
+    accessibility::{AccessibilityNode, AccessibilityRole},
+    input_text::{ClearOnSubmit, InputText, Placeholder, TextSubmitted},
     item_list::ItemList,
-    widget::{Widget, WidgetEvent, WidgetLabel},
+    key_bindings::{WidgetKeyBindings, CONSOLE_TOGGLE},
+    widget::{Widget, WidgetEvent, WidgetFrames, WidgetLabel},
 };
 
 const CONSOLE_HEIGHT_PERC: f32 = 80.0;
@@ -99,17 +101,27 @@ impl Widget for Console {
         };
 
         let command_text = InputText::build(CommandTextLabel, commands, asset_server);
+        commands
+            .entity(command_text)
+            .insert(Placeholder::new("Type a command...").shown_when_focused())
+            .insert(ClearOnSubmit);
         let log_items = ItemList::build(LogListLabel, commands, asset_server);
 
-        let entity = commands
-            .spawn_bundle(panel)
+        let mut console = commands.spawn_bundle(panel);
+        let entity = console.id();
+
+        console
             .add_child(command_text)
             .add_child(log_items)
             .insert(Name::new(label.name()))
+            .insert(AccessibilityNode {
+                expanded: Some(false),
+                ..AccessibilityNode::new(AccessibilityRole::Dialog).with_name(label.name())
+            })
             .insert(label)
             .insert(Console::default())
-            .insert(Visibility { is_visible: false })
-            .id();
+            .insert(WidgetFrames(vec![entity]))
+            .insert(Visibility { is_visible: false });
 
         commands.insert_resource(ConsoleMeta {
             command_text,
@@ -123,47 +135,44 @@ impl Widget for Console {
     }
 }
 
-fn toggle_console(mut meta: ResMut<ConsoleMeta>, input: Res<Input<KeyCode>>) {
-    if input.any_just_pressed([KeyCode::Grave, KeyCode::Apostrophe])
-        && input.pressed(KeyCode::LControl)
-    {
+fn toggle_console(
+    mut meta: ResMut<ConsoleMeta>,
+    input: Res<Input<KeyCode>>,
+    bindings: Res<WidgetKeyBindings>,
+) {
+    if bindings.just_pressed(CONSOLE_TOGGLE, &input) {
         meta.toggle_visibility();
     }
 }
 
 fn apply_command(
-    input: Res<Input<KeyCode>>,
+    mut reader: EventReader<TextSubmitted>,
     meta: Res<ConsoleMeta>,
-    mut q_input_text: Query<&mut InputText>,
     mut q_item_list: Query<&mut ItemList>,
     mut writer: EventWriter<CommandIssued>,
 ) {
-    if input.any_just_pressed([KeyCode::Return, KeyCode::NumpadEnter]) == false {
-        return;
-    }
-
-    let mut input_text = q_input_text
-        .get_mut(meta.command_text)
-        .expect("Every console should have an input text");
-
-    let cmd = input_text.take();
+    for evt in reader.iter() {
+        if evt.entity != meta.command_text || evt.text.trim().is_empty() {
+            continue;
+        }
 
-    let mut item_list = q_item_list
-        .get_mut(meta.log_items)
-        .expect("Every console should have an item list");
+        let mut item_list = q_item_list
+            .get_mut(meta.log_items)
+            .expect("Every console should have an item list");
 
-    item_list.items.push(cmd.clone());
+        item_list.items.push(evt.text.clone());
 
-    writer.send(CommandIssued(meta.entity, cmd))
+        writer.send(CommandIssued(meta.entity, evt.text.clone()))
+    }
 }
 
 fn console_animation(
-    mut q: Query<(&mut Style, &mut Visibility), With<Console>>,
+    mut q: Query<(&mut Style, &mut Visibility, &mut AccessibilityNode), With<Console>>,
     time: Res<Time>,
     mut meta: ResMut<ConsoleMeta>,
     mut writer: EventWriter<NavRequest>,
 ) {
-    if let Ok((mut style, mut visibility)) = q.get_mut(meta.entity) {
+    if let Ok((mut style, mut visibility, mut node)) = q.get_mut(meta.entity) {
         if meta.direction == 0 {
             return;
         } else {
@@ -178,12 +187,14 @@ fn console_animation(
                 style.position.top = Val::Percent(0.0);
                 meta.direction = 0;
                 meta.visible = true;
+                node.expanded = Some(true);
 
                 writer.send(NavRequest::FocusOn(meta.command_text));
             } else if meta.direction == -1 && top <= -CONSOLE_HEIGHT_PERC {
                 style.position.top = Val::Percent(-CONSOLE_HEIGHT_PERC);
                 meta.direction = 0;
                 meta.visible = false;
+                node.expanded = Some(false);
             } else {
                 style.position.top = Val::Percent(top);
             }
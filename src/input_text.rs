@@ -1,51 +1,747 @@
-use std::time::Duration;
+use std::{collections::VecDeque, ops::Range};
 
-use crate::widget::{Widget, WidgetLabel};
-use bevy::{prelude::*, ui::FocusPolicy};
+use crate::{
+    accessibility::{AccessibilityNode, AccessibilityRole},
+    button::{ButtonLabel, ButtonToggled, TextButton, Toggled},
+    clipboard::WidgetClipboard,
+    key_bindings::{
+        key_code_for_char, KeyRepeat, WidgetKeyBindings, TEXT_CARET_DOWN, TEXT_CARET_END,
+        TEXT_CARET_HOME, TEXT_CARET_LEFT, TEXT_CARET_RIGHT, TEXT_CARET_UP, TEXT_COPY, TEXT_CUT,
+        TEXT_DELETE_BACK, TEXT_DELETE_FORWARD, TEXT_DELETE_WORD_BACK, TEXT_PASTE, TEXT_REDO,
+        TEXT_SELECT_ALL, TEXT_SELECT_DOWN, TEXT_SELECT_END, TEXT_SELECT_HOME, TEXT_SELECT_LEFT,
+        TEXT_SELECT_RIGHT, TEXT_SELECT_UP, TEXT_SELECT_WORD_LEFT, TEXT_SELECT_WORD_RIGHT,
+        TEXT_SUBMIT, TEXT_UNDO, TEXT_WORD_LEFT, TEXT_WORD_RIGHT,
+    },
+    pointer::{cursor_position, DoubleClick, PointerDown, PointerTarget},
+    theme::WidgetTheme,
+    widget::{Disabled, StringLabel, Widget, WidgetEvent, WidgetFrames, WidgetLabel},
+};
+use bevy::{prelude::*, text::DefaultTextPipeline, ui::FocusPolicy};
 use bevy_ui_navigation::prelude::{FocusState, Focusable, NavRequest};
+use regex::Regex;
+use unicode_segmentation::UnicodeSegmentation;
+
+// Room kept between the caret and the edges of the field when scrolling.
+const SCROLL_MARGIN: f32 = 4.0;
+const REVEAL_BUTTON_WIDTH: f32 = 40.0;
+
+// How many edits can be undone by default.
+const HISTORY_DEPTH: usize = 100;
 
 #[derive(SystemLabel)]
 struct RemoveFocus;
 
+// Systems handling the editing keys of focused fields.
+#[derive(SystemLabel)]
+pub(crate) struct InputTextKeys;
+
+#[derive(SystemLabel)]
+pub(crate) struct InputTextPointer;
+
 pub(super) struct InputTextPlugin;
 
 impl Plugin for InputTextPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<InputText>()
+            .register_type::<Placeholder>()
+            .register_type::<Invalid>()
+            .register_type::<PasswordMask>()
+            .register_type::<KeyRepeat>()
+            .register_type::<ClearOnSubmit>()
+            .add_event::<InputValidationChanged>()
+            .add_event::<TextChanged>()
+            .add_event::<TextSubmitted>()
             .add_system(toggle_focus_visibility.label(RemoveFocus))
             .add_system(hide_caret_when_lose_focus.after(RemoveFocus))
             .add_system(update_text_section)
-            .add_system(update_text_backspace)
+            .add_system(update_accessibility_value)
+            .add_system(update_text_editing.label(InputTextKeys))
+            .add_system(update_text_clipboard)
+            .add_system(update_text_pointer.label(InputTextPointer))
             .add_system(update_text_characters)
-            .add_system(update_text_caret);
+            .add_system(update_text_caret)
+            .add_system(update_text_color)
+            .add_system(update_placeholder)
+            .add_system(validate_text)
+            .add_system(update_reveal_button)
+            .add_system(toggle_password_reveal)
+            .add_system(update_reveal_button_label)
+            .add_system(dispatch_text_changed)
+            .add_system(submit_text.label(InputTextKeys))
+            .add_system(scroll_to_caret);
     }
 }
 
-#[derive(Component, Default, Reflect)]
+#[derive(Component, Reflect)]
 #[reflect(Component)]
 pub struct InputText {
     text: String,
+    // Byte index on `text`, always on a grapheme boundary.
+    caret: usize,
+    // Where the selection started, the caret is the other end of it.
+    anchor: Option<usize>,
+    #[reflect(ignore)]
+    history: EditHistory,
+    history_depth: usize,
+    // Accepts line breaks, used by `TextArea`.
+    multiline: bool,
+    // Lines the caret was asked to move up or down, resolved by the widget from its layout.
+    #[reflect(ignore)]
+    line_move: Option<(isize, bool)>,
+}
+
+impl Default for InputText {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            caret: 0,
+            anchor: None,
+            history: EditHistory::default(),
+            history_depth: HISTORY_DEPTH,
+            multiline: false,
+            line_move: None,
+        }
+    }
+}
+
+// Editing state shared by every widget built on `InputText`.
+#[derive(Component, Default)]
+pub(crate) struct InputTextEditing {
+    // Text last reported by `TextChanged`.
+    reported_text: String,
+    // Editing key being held down on this field.
+    repeat: Option<RepeatState>,
+}
+
+struct RepeatState {
+    // Index on the editing actions table.
+    action: usize,
+    timer: Timer,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CharClass {
+    Alphabetic,
+    Numeric,
+    Alphanumeric,
+    Whitespace,
+    Punctuation,
+}
+
+impl CharClass {
+    pub fn contains(&self, c: char) -> bool {
+        match self {
+            CharClass::Alphabetic => c.is_alphabetic(),
+            CharClass::Numeric => c.is_numeric(),
+            CharClass::Alphanumeric => c.is_alphanumeric(),
+            CharClass::Whitespace => c.is_whitespace(),
+            CharClass::Punctuation => c.is_ascii_punctuation(),
+        }
+    }
+}
+
+// Rejects typed and pasted text before it reaches the `InputText`. Every set rule must accept
+// each inserted char, only the max length looks at the whole text.
+#[derive(Component, Default)]
+pub struct InputFilter {
+    // Maximum length in graphemes.
+    pub max_length: Option<usize>,
+    // Allowed char classes, any char is allowed when empty.
+    pub classes: Vec<CharClass>,
+    pub predicate: Option<Box<dyn Fn(char) -> bool + Send + Sync>>,
+    // Matched against every char on its own, like `[0-9a-f]`.
+    pub pattern: Option<Regex>,
+}
+
+impl InputFilter {
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    pub fn with_classes(mut self, classes: impl Into<Vec<CharClass>>) -> Self {
+        self.classes = classes.into();
+        self
+    }
+
+    pub fn with_predicate(
+        mut self,
+        predicate: impl Fn(char) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.predicate = Some(Box::new(predicate));
+        self
+    }
+
+    // Partial text rarely matches a pattern meant for the whole text, so those are checked
+    // with `InputValidator::pattern` instead.
+    pub fn with_pattern(mut self, pattern: &str) -> Result<Self, regex::Error> {
+        self.pattern = Some(Regex::new(&format!("^(?:{pattern})$"))?);
+        Ok(self)
+    }
+
+    pub fn accepts_char(&self, c: char) -> bool {
+        (self.classes.is_empty() || self.classes.iter().any(|class| class.contains(c)))
+            && self
+                .predicate
+                .as_ref()
+                .map_or(true, |predicate| predicate(c))
+            && self
+                .pattern
+                .as_ref()
+                .map_or(true, |pattern| pattern.is_match(c.encode_utf8(&mut [0; 4])))
+    }
+
+    // Returns the part of `s` which can be inserted at the caret of `input`.
+    pub fn filter_insert(&self, input: &InputText, s: &str) -> String {
+        let mut accepted = s
+            .chars()
+            .filter(|&c| self.accepts_char(c))
+            .collect::<String>();
+
+        if let Some(max_length) = self.max_length {
+            let kept =
+                input.text.graphemes(true).count() - input.selected_text().graphemes(true).count();
+            let remaining = max_length.saturating_sub(kept);
+            accepted = accepted.graphemes(true).take(remaining).collect();
+        }
+
+        accepted
+    }
+}
+
+// Marks the field `Invalid` whenever the function returns `false` for its text. Unlike
+// `InputFilter`, invalid text is still accepted.
+#[derive(Component)]
+pub struct InputValidator(pub Box<dyn Fn(&str) -> bool + Send + Sync>);
+
+impl InputValidator {
+    pub fn new(validator: impl Fn(&str) -> bool + Send + Sync + 'static) -> Self {
+        Self(Box::new(validator))
+    }
+
+    // Valid when the pattern matches the whole text.
+    pub fn pattern(pattern: &str) -> Result<Self, regex::Error> {
+        let regex = Regex::new(&format!("^(?:{pattern})$"))?;
+        Ok(Self::new(move |text| regex.is_match(text)))
+    }
+}
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Invalid;
+
+pub struct InputValidationChanged {
+    pub entity: Entity,
+    pub valid: bool,
+}
+
+impl WidgetEvent for InputValidationChanged {
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+}
+
+pub struct TextChanged {
+    pub entity: Entity,
+    pub text: String,
+}
+
+impl WidgetEvent for TextChanged {
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+}
+
+pub struct TextSubmitted {
+    pub entity: Entity,
+    pub text: String,
+}
+
+impl WidgetEvent for TextSubmitted {
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+}
+
+// Empties the field after its text is submitted.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct ClearOnSubmit;
+
+// Displays every grapheme of the text as `mask`, unless it's revealed. The masked text can't
+// be copied or cut to the clipboard.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct PasswordMask {
+    pub mask: char,
+    pub revealed: bool,
+    // Spawns a toggle button inside the field which reveals the text while checked.
+    pub reveal_button: bool,
+}
+
+impl Default for PasswordMask {
+    fn default() -> Self {
+        Self {
+            mask: '•',
+            revealed: false,
+            reveal_button: false,
+        }
+    }
+}
+
+impl PasswordMask {
+    pub fn with_mask(mut self, mask: char) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    pub fn with_reveal_button(mut self) -> Self {
+        self.reveal_button = true;
+        self
+    }
+
+    pub fn is_masked(&self) -> bool {
+        self.revealed == false
+    }
+
+    pub fn display(&self, text: &str) -> String {
+        if self.is_masked() {
+            std::iter::repeat(self.mask)
+                .take(text.graphemes(true).count())
+                .collect()
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+#[derive(Component)]
+struct PasswordRevealButton(Entity);
+
+// Hint shown in a dimmed color while the field is empty. It's only displayed, so it never
+// becomes part of the `InputText` text.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Placeholder {
+    pub text: String,
+    pub show_when_focused: bool,
+}
+
+impl Placeholder {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            show_when_focused: false,
+        }
+    }
+
+    pub fn shown_when_focused(mut self) -> Self {
+        self.show_when_focused = true;
+        self
+    }
+}
+
+#[derive(Clone)]
+struct TextSnapshot {
+    text: String,
+    caret: usize,
+    anchor: Option<usize>,
+}
+
+#[derive(Default)]
+struct EditHistory {
+    undo: VecDeque<TextSnapshot>,
+    redo: Vec<TextSnapshot>,
+    // Whether the last entry is a typing run which further typing should be merged into.
+    typing: bool,
 }
 
 impl InputText {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    // Replaces the whole text and places the caret at its end. Meant for updates made by code,
+    // so it isn't recorded and the history is cleared, since its edits no longer apply.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.caret = self.text.len();
+        self.anchor = None;
+        self.history = EditHistory::default();
+    }
+
+    // The taken text is kept on the history, so it can be brought back with `undo`.
     pub fn take(&mut self) -> String {
-        std::mem::take(&mut self.text)
+        let mut text = String::new();
+        self.edit(false, |input| {
+            input.caret = 0;
+            input.anchor = None;
+            text = std::mem::take(&mut input.text);
+        });
+        text
+    }
+
+    pub fn undo(&mut self) -> bool {
+        let snapshot = match self.history.undo.pop_back() {
+            Some(snapshot) => snapshot,
+            None => return false,
+        };
+
+        self.history.redo.push(self.snapshot());
+        self.restore(snapshot);
+        true
+    }
+
+    pub fn redo(&mut self) -> bool {
+        let snapshot = match self.history.redo.pop() {
+            Some(snapshot) => snapshot,
+            None => return false,
+        };
+
+        self.history.undo.push_back(self.snapshot());
+        self.restore(snapshot);
+        true
+    }
+
+    pub fn history_depth(&self) -> usize {
+        self.history_depth
+    }
+
+    pub fn set_history_depth(&mut self, depth: usize) {
+        self.history_depth = depth;
+        let excess = self.history.undo.len().saturating_sub(depth);
+        self.history.undo.drain(..excess);
+    }
+
+    pub fn caret(&self) -> usize {
+        self.caret
+    }
+
+    pub fn set_caret(&mut self, caret: usize) {
+        self.move_to(self.boundary(caret), false);
+    }
+
+    // Moves the caret while keeping the selection anchor in place.
+    pub fn select_to(&mut self, caret: usize) {
+        self.move_to(self.boundary(caret), true);
+    }
+
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor?;
+        (anchor != self.caret).then(|| anchor.min(self.caret)..anchor.max(self.caret))
+    }
+
+    pub fn selected_text(&self) -> &str {
+        self.selection().map_or("", |range| &self.text[range])
+    }
+
+    pub fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.caret = self.text.len();
+    }
+
+    pub fn select_word_at(&mut self, index: usize) {
+        let index = self.boundary(index);
+        let start = self.text[..index]
+            .rfind(char::is_whitespace)
+            .map_or(0, |i| self.next_grapheme(i));
+        let end = self.text[index..]
+            .find(char::is_whitespace)
+            .map_or(self.text.len(), |i| index + i);
+
+        self.anchor = Some(start);
+        self.caret = end;
+    }
+
+    // Typed words are undone at once, whitespace starts a new history entry.
+    pub fn insert(&mut self, c: char) {
+        self.edit(c.is_whitespace() == false, |input| {
+            input.remove_selection();
+            input.text.insert(input.caret, c);
+            input.caret += c.len_utf8();
+        });
+    }
+
+    pub fn insert_str(&mut self, s: &str) {
+        self.edit(false, |input| {
+            input.remove_selection();
+            input.text.insert_str(input.caret, s);
+            input.caret += s.len();
+        });
+    }
+
+    // Returns `false` when there was nothing selected.
+    pub fn delete_selection(&mut self) -> bool {
+        let mut deleted = false;
+        self.edit(false, |input| deleted = input.remove_selection());
+        deleted
+    }
+
+    pub fn move_left(&mut self, select: bool) {
+        match self.selection() {
+            Some(range) if select == false => self.move_to(range.start, false),
+            _ => self.move_to(self.prev_grapheme(self.caret), select),
+        }
+    }
+
+    pub fn move_right(&mut self, select: bool) {
+        match self.selection() {
+            Some(range) if select == false => self.move_to(range.end, false),
+            _ => self.move_to(self.next_grapheme(self.caret), select),
+        }
+    }
+
+    // Multiline text moves within the line holding the caret.
+    pub fn move_home(&mut self, select: bool) {
+        let start = if self.multiline {
+            self.text[..self.caret].rfind('\n').map_or(0, |i| i + 1)
+        } else {
+            0
+        };
+        self.move_to(start, select);
+    }
+
+    pub fn move_end(&mut self, select: bool) {
+        let end = if self.multiline {
+            self.text[self.caret..]
+                .find('\n')
+                .map_or(self.text.len(), |i| self.caret + i)
+        } else {
+            self.text.len()
+        };
+        self.move_to(end, select);
+    }
+
+    // A single line moves to the start, since there's nothing above it.
+    pub fn move_up(&mut self, select: bool) {
+        if self.multiline {
+            self.line_move = Some((-1, select));
+        } else {
+            self.move_to(0, select);
+        }
+    }
+
+    pub fn move_down(&mut self, select: bool) {
+        if self.multiline {
+            self.line_move = Some((1, select));
+        } else {
+            self.move_to(self.text.len(), select);
+        }
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        self.multiline
+    }
+
+    pub(crate) fn new_multiline() -> Self {
+        Self {
+            multiline: true,
+            ..default()
+        }
+    }
+
+    pub(crate) fn has_line_move(&self) -> bool {
+        self.line_move.is_some()
+    }
+
+    pub(crate) fn take_line_move(&mut self) -> Option<(isize, bool)> {
+        self.line_move.take()
+    }
+
+    pub fn move_word_left(&mut self, select: bool) {
+        self.move_to(self.prev_word(self.caret), select);
+    }
+
+    pub fn move_word_right(&mut self, select: bool) {
+        self.move_to(self.next_word(self.caret), select);
+    }
+
+    pub fn delete_back(&mut self) {
+        self.edit(false, |input| {
+            if input.remove_selection() == false {
+                let start = input.prev_grapheme(input.caret);
+                input.text.replace_range(start..input.caret, "");
+                input.caret = start;
+            }
+        });
+    }
+
+    pub fn delete_forward(&mut self) {
+        self.edit(false, |input| {
+            if input.remove_selection() == false {
+                let end = input.next_grapheme(input.caret);
+                input.text.replace_range(input.caret..end, "");
+            }
+        });
+    }
+
+    pub fn delete_word_back(&mut self) {
+        self.edit(false, |input| {
+            if input.remove_selection() == false {
+                let start = input.prev_word(input.caret);
+                input.text.replace_range(start..input.caret, "");
+                input.caret = start;
+            }
+        });
+    }
+
+    // Records the state before `f` on the history, unless the text didn't change or `f` is
+    // typing right after another typing edit.
+    fn edit(&mut self, typing: bool, f: impl FnOnce(&mut Self)) {
+        let before = self.snapshot();
+        f(self);
+
+        if self.text == before.text {
+            return;
+        }
+
+        self.history.redo.clear();
+        if typing == false || self.history.typing == false {
+            self.history.undo.push_back(before);
+            if self.history.undo.len() > self.history_depth {
+                self.history.undo.pop_front();
+            }
+        }
+        self.history.typing = typing;
+    }
+
+    fn snapshot(&self) -> TextSnapshot {
+        TextSnapshot {
+            text: self.text.clone(),
+            caret: self.caret,
+            anchor: self.anchor,
+        }
+    }
+
+    fn restore(&mut self, snapshot: TextSnapshot) {
+        self.text = snapshot.text;
+        self.caret = snapshot.caret;
+        self.anchor = snapshot.anchor;
+        self.history.typing = false;
+    }
+
+    fn remove_selection(&mut self) -> bool {
+        let range = match self.selection() {
+            Some(range) => range,
+            None => return false,
+        };
+
+        self.caret = range.start;
+        self.anchor = None;
+        self.text.replace_range(range, "");
+        true
+    }
+
+    fn move_to(&mut self, caret: usize, select: bool) {
+        self.history.typing = false;
+        if select {
+            self.anchor.get_or_insert(self.caret);
+        } else {
+            self.anchor = None;
+        }
+        self.caret = caret;
+    }
+
+    // Rounds `index` down to the start of the grapheme it falls into.
+    fn boundary(&self, index: usize) -> usize {
+        if index >= self.text.len() {
+            return self.text.len();
+        }
+
+        self.text
+            .grapheme_indices(true)
+            .map(|(i, _)| i)
+            .take_while(|&i| i <= index)
+            .last()
+            .unwrap_or(0)
+    }
+
+    fn prev_grapheme(&self, index: usize) -> usize {
+        self.text[..index]
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(i, _)| i)
+    }
+
+    fn next_grapheme(&self, index: usize) -> usize {
+        self.text[index..]
+            .graphemes(true)
+            .next()
+            .map_or(index, |grapheme| index + grapheme.len())
+    }
+
+    // Skips any whitespace before the caret and then the word itself.
+    fn prev_word(&self, index: usize) -> usize {
+        let before = self.text[..index].trim_end();
+        before
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(0, |(i, c)| i + c.len_utf8())
+    }
+
+    // Skips the rest of the current word and then any whitespace after it.
+    fn next_word(&self, index: usize) -> usize {
+        let after = &self.text[index..];
+        let word_end = after.find(char::is_whitespace).unwrap_or(after.len());
+        let rest = &after[word_end..];
+        index + word_end + (rest.len() - rest.trim_start().len())
     }
 }
 
 #[derive(Component)]
 struct InputTextMeta {
+    // Clipped area the text is scrolled within.
+    content_entity: Entity,
+    // Row holding the text nodes.
+    scroll_entity: Entity,
+    // How far the text is scrolled to the left, in pixels.
+    scroll: f32,
+    // Text before the selection, or the caret when nothing is selected.
     text_entity: Entity,
-    caret_entity: Entity,
-    caret_visible: bool,
-    caret_timer: Timer,
+    // Highlighted node holding the selected text and the caret.
+    selection_entity: Entity,
+    selected_entity: Entity,
+    // Text after the selection.
+    tail_entity: Entity,
+    placeholder_entity: Entity,
+}
+
+// Blinking caret of a widget built on `InputText`, only shown while the widget is focused.
+#[derive(Component)]
+pub(crate) struct InputTextCaret {
+    entity: Entity,
+    visible: bool,
+    timer: Timer,
+}
+
+impl InputTextCaret {
+    // The caret node needs `InputTextDisplayCaret` and a `Focusable`, so the focus can be moved
+    // to it when the widget is hidden.
+    pub(crate) fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            visible: false,
+            timer: Timer::from_seconds(0.5, true),
+        }
+    }
+
+    pub(crate) fn entity(&self) -> Entity {
+        self.entity
+    }
 }
 
 #[derive(Component)]
 struct InputTextDisplayText;
 
 #[derive(Component)]
-struct InputTextDisplayCaret;
+struct InputTextDisplaySelection;
+
+#[derive(Component)]
+struct InputTextScroll;
+
+#[derive(Component)]
+pub(crate) struct InputTextDisplayCaret;
 
 impl Widget for InputText {
     fn build<L: WidgetLabel>(
@@ -66,16 +762,41 @@ impl Widget for InputText {
 
         let input_text = commands
             .spawn_bundle(TextBundle::from_section(
-                "Some fancy command here!",
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 15.0,
+                    color: WidgetTheme::default().text,
+                },
+            ))
+            .insert(InputTextDisplayText)
+            .id();
+
+        let input_tail = commands
+            .spawn_bundle(TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 15.0,
+                    color: WidgetTheme::default().text,
+                },
+            ))
+            .insert(InputTextDisplayText)
+            .id();
+
+        let input_selected = commands
+            .spawn_bundle(TextBundle::from_section(
+                "",
                 TextStyle {
                     font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                     font_size: 15.0,
-                    color: Color::rgb(0.7, 0.7, 0.7).into(),
+                    color: WidgetTheme::default().text,
                 },
             ))
             .insert(InputTextDisplayText)
             .id();
 
+        // The caret takes no room, so it sits between the text nodes without pushing the tail.
         let input_caret = commands
             .spawn_bundle(
                 TextBundle::from_section(
@@ -88,6 +809,7 @@ impl Widget for InputText {
                 )
                 .with_style(Style {
                     display: Display::None,
+                    size: Size::new(Val::Px(0.0), Val::Auto),
                     ..default()
                 }),
             )
@@ -95,65 +817,144 @@ impl Widget for InputText {
             .insert(InputTextDisplayCaret)
             .id();
 
-        let panel_bg = commands
+        let input_placeholder = commands
+            .spawn_bundle(
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                        font_size: 15.0,
+                        color: WidgetTheme::default().placeholder_text,
+                    },
+                )
+                .with_style(Style {
+                    display: Display::None,
+                    ..default()
+                }),
+            )
+            .id();
+
+        // Reversing the direction moves the caret to the start of the selection.
+        let input_selection = commands
             .spawn_bundle(NodeBundle {
                 style: Style {
-                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
-                    padding: UiRect::new(Val::Px(2.0), Val::Px(2.0), Val::Px(8.0), Val::Px(8.0)),
                     align_items: AlignItems::Center,
-                    justify_content: JustifyContent::FlexStart,
+                    flex_direction: FlexDirection::Row,
                     ..default()
                 },
                 focus_policy: FocusPolicy::Pass,
-                color: Color::rgba(0.1, 0.1, 0.1, 0.9).into(),
+                color: Color::NONE.into(),
                 ..default()
             })
-            .add_child(input_text)
+            .add_child(input_selected)
             .add_child(input_caret)
+            .insert(InputTextDisplaySelection)
             .id();
 
-        commands
-            .spawn_bundle(input_panel)
+        // Grows with the text and is moved to the left to scroll it.
+        let input_scroll = commands
+            .spawn_bundle(NodeBundle {
+                style: Style {
+                    align_items: AlignItems::Center,
+                    flex_shrink: 0.0,
+                    position: UiRect::new(
+                        Val::Px(0.0),
+                        Val::Undefined,
+                        Val::Undefined,
+                        Val::Undefined,
+                    ),
+                    ..default()
+                },
+                focus_policy: FocusPolicy::Pass,
+                color: Color::NONE.into(),
+                ..default()
+            })
+            .add_child(input_text)
+            .add_child(input_selection)
+            .add_child(input_tail)
+            .add_child(input_placeholder)
+            .insert(InputTextScroll)
+            .id();
+
+        let panel_bg = commands
+            .spawn_bundle(NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    padding: UiRect::new(Val::Px(2.0), Val::Px(2.0), Val::Px(8.0), Val::Px(8.0)),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::FlexStart,
+                    overflow: Overflow::Hidden,
+                    ..default()
+                },
+                focus_policy: FocusPolicy::Pass,
+                color: Color::rgba(0.1, 0.1, 0.1, 0.9).into(),
+                ..default()
+            })
+            .add_child(input_scroll)
+            .id();
+
+        let mut panel = commands.spawn_bundle(input_panel);
+        let panel_entity = panel.id();
+
+        panel
             .add_child(panel_bg)
+            .insert(WidgetFrames(vec![panel_entity, panel_bg]))
             .insert(Name::new(label.name()))
+            .insert(AccessibilityNode::new(AccessibilityRole::TextBox).with_name(label.name()))
             .insert(label)
             .insert(Focusable::new().blocked())
+            .insert(Interaction::default())
+            .insert(PointerTarget::default())
             .insert(InputText::default())
             .insert(InputTextMeta {
+                content_entity: panel_bg,
+                scroll_entity: input_scroll,
+                scroll: 0.0,
                 text_entity: input_text,
-                caret_entity: input_caret,
-                caret_visible: false,
-                caret_timer: Timer::from_seconds(0.5, true),
+                selection_entity: input_selection,
+                selected_entity: input_selected,
+                tail_entity: input_tail,
+                placeholder_entity: input_placeholder,
             })
+            .insert(InputTextCaret::new(input_caret))
+            .insert(InputTextEditing::default())
             .id()
     }
 }
 
 fn toggle_focus_visibility(
     mut q: Query<
-        (&mut Focusable, &ComputedVisibility, &InputTextMeta),
+        (
+            &mut Focusable,
+            &ComputedVisibility,
+            &InputTextCaret,
+            Option<&Disabled>,
+        ),
         (With<InputText>, Changed<ComputedVisibility>),
     >,
     mut writer: EventWriter<NavRequest>,
 ) {
-    for (mut focus, visibility, meta) in &mut q {
+    for (mut focus, visibility, caret, disabled) in &mut q {
         if visibility.is_visible() == false && focus.state() != FocusState::Blocked {
             if focus.block() == false {
                 // TODO: Change it later on when it's possible to remove focus.
-                writer.send(NavRequest::FocusOn(meta.caret_entity));
+                writer.send(NavRequest::FocusOn(caret.entity));
             }
-        } else if visibility.is_visible() && focus.state() == FocusState::Blocked {
+        } else if visibility.is_visible()
+            && focus.state() == FocusState::Blocked
+            && disabled.is_none()
+        {
             focus.unblock();
         }
     }
 }
 
 fn hide_caret_when_lose_focus(
-    mut q: Query<(&InputTextMeta, &Focusable), (With<InputText>, Changed<Focusable>)>,
+    mut q: Query<(&InputTextCaret, &Focusable), (With<InputText>, Changed<Focusable>)>,
     mut q_caret: Query<&mut Style, With<InputTextDisplayCaret>>,
 ) {
-    for (meta, focus) in &mut q {
-        if let Ok(mut style) = q_caret.get_mut(meta.caret_entity) {
+    for (caret, focus) in &mut q {
+        if let Ok(mut style) = q_caret.get_mut(caret.entity) {
             if focus.state() != FocusState::Focused && &style.display == &Display::Flex {
                 style.display = Display::None;
             }
@@ -162,75 +963,381 @@ fn hide_caret_when_lose_focus(
 }
 
 fn update_text_section(
-    q: Query<(&InputText, &InputTextMeta), Changed<InputText>>,
+    q: Query<(
+        Entity,
+        &InputText,
+        &InputTextMeta,
+        Option<&PasswordMask>,
+        ChangeTrackers<InputText>,
+        Option<ChangeTrackers<PasswordMask>>,
+    )>,
     mut q_child: Query<&mut Text, With<InputTextDisplayText>>,
+    mut q_selection: Query<(&mut Style, &mut UiColor), With<InputTextDisplaySelection>>,
+    removed_masks: RemovedComponents<PasswordMask>,
+    theme: Res<WidgetTheme>,
+) {
+    let removed_masks = removed_masks.iter().collect::<Vec<_>>();
+
+    for (entity, input_text, meta, mask, input_tracker, mask_tracker) in &q {
+        let mask_changed = mask_tracker.map_or(false, |tracker| tracker.is_changed());
+        if input_tracker.is_changed() == false
+            && mask_changed == false
+            && theme.is_changed() == false
+            && removed_masks.contains(&entity) == false
+        {
+            continue;
+        }
+
+        let selection = input_text.selection();
+        let range = selection
+            .clone()
+            .unwrap_or(input_text.caret..input_text.caret);
+
+        for (text_entity, value) in [
+            (meta.text_entity, &input_text.text[..range.start]),
+            (meta.selected_entity, &input_text.text[range.clone()]),
+            (meta.tail_entity, &input_text.text[range.end..]),
+        ] {
+            q_child
+                .get_mut(text_entity)
+                .expect("Every InputText should have its text children")
+                .sections[0]
+                .value = mask.map_or(value.to_string(), |mask| mask.display(value));
+        }
+
+        let (mut style, mut color) = q_selection
+            .get_mut(meta.selection_entity)
+            .expect("Every InputText should have a selection child");
+
+        style.flex_direction = if input_text.caret == range.start && selection.is_some() {
+            FlexDirection::RowReverse
+        } else {
+            FlexDirection::Row
+        };
+
+        color.0 = if selection.is_some() {
+            theme.text_selection
+        } else {
+            Color::NONE
+        };
+    }
+}
+
+fn update_accessibility_value(
+    mut q: Query<
+        (&InputText, &mut AccessibilityNode, Option<&PasswordMask>),
+        Or<(Changed<InputText>, Changed<PasswordMask>)>,
+    >,
 ) {
-    for (input_text, meta) in &q {
-        q_child
-            .get_mut(meta.text_entity)
-            .expect("Every InputText should have a text child")
-            .sections[0]
-            .value = input_text.text.clone();
+    for (input_text, mut node, mask) in &mut q {
+        node.value = Some(mask.map_or(input_text.text.clone(), |mask| {
+            mask.display(&input_text.text)
+        }));
     }
 }
 
 fn update_text_characters(
-    mut q: Query<(&Focusable, &mut InputText)>,
+    mut q: Query<(&Focusable, &mut InputText, Option<&InputFilter>), Without<Disabled>>,
     mut events: EventReader<ReceivedCharacter>,
+    input_keycode: Res<Input<KeyCode>>,
+    bindings: Res<WidgetKeyBindings>,
 ) {
-    for (focus, mut input_text) in &mut q {
-        if focus.state() == FocusState::Focused {
-            for evt in events.iter() {
-                input_text.text.push(evt.char);
+    // Ctrl on its own never types, but Ctrl+Alt is how AltGr is reported on some platforms.
+    let ctrl = input_keycode.any_pressed([KeyCode::LControl, KeyCode::RControl]);
+    let alt = input_keycode.any_pressed([KeyCode::LAlt, KeyCode::RAlt]);
+
+    // Enter, Backspace, Tab and the like are received as control characters.
+    let mut chars = events
+        .iter()
+        .map(|evt| evt.char)
+        .filter(|c| c.is_control() == false)
+        .collect::<Vec<_>>();
+    if ctrl && alt == false {
+        chars.clear();
+    }
+
+    // Each shortcut swallows the character of its key, or the first one when Alt changed it.
+    for key in bindings.text_shortcut_keys(&input_keycode) {
+        let typed = chars
+            .iter()
+            .position(|&c| key_code_for_char(c) == Some(key))
+            .or_else(|| (alt && chars.is_empty() == false).then_some(0));
+        if let Some(index) = typed {
+            chars.remove(index);
+        }
+    }
+
+    for (focus, mut input_text, filter) in &mut q {
+        if focus.state() != FocusState::Focused {
+            continue;
+        }
+
+        for &c in &chars {
+            let accepted = match filter {
+                Some(filter) => filter.filter_insert(&input_text, &c.to_string()),
+                None => c.to_string(),
+            };
+
+            for c in accepted.chars() {
+                input_text.insert(c);
             }
         }
     }
 }
 
-fn update_text_backspace(
-    mut q: Query<(&Focusable, &mut InputText)>,
+fn update_text_editing(
+    mut q: Query<
+        (
+            &Focusable,
+            &mut InputText,
+            &mut InputTextEditing,
+            Option<&KeyRepeat>,
+        ),
+        Without<Disabled>,
+    >,
     input_keycode: Res<Input<KeyCode>>,
-    mut timer: Local<Timer>,
+    bindings: Res<WidgetKeyBindings>,
+    global_repeat: Res<KeyRepeat>,
     time: Res<Time>,
 ) {
-    for (focus, mut input_text) in &mut q {
-        if focus.state() == FocusState::Focused {
-            timer.tick(time.delta());
-
-            let backspace = if input_keycode.pressed(KeyCode::Back) && timer.finished() {
-                timer.set_duration(Duration::from_millis(100));
-                timer.reset();
-                true
-            } else if input_keycode.just_pressed(KeyCode::Back) {
-                true
-            } else {
-                false
+    let actions: [(&str, fn(&mut InputText)); 21] = [
+        (TEXT_DELETE_BACK, InputText::delete_back),
+        (TEXT_DELETE_FORWARD, InputText::delete_forward),
+        (TEXT_DELETE_WORD_BACK, InputText::delete_word_back),
+        (TEXT_UNDO, |t| {
+            t.undo();
+        }),
+        (TEXT_REDO, |t| {
+            t.redo();
+        }),
+        (TEXT_CARET_LEFT, |t| t.move_left(false)),
+        (TEXT_CARET_RIGHT, |t| t.move_right(false)),
+        (TEXT_CARET_HOME, |t| t.move_home(false)),
+        (TEXT_CARET_END, |t| t.move_end(false)),
+        (TEXT_WORD_LEFT, |t| t.move_word_left(false)),
+        (TEXT_WORD_RIGHT, |t| t.move_word_right(false)),
+        (TEXT_SELECT_LEFT, |t| t.move_left(true)),
+        (TEXT_SELECT_RIGHT, |t| t.move_right(true)),
+        (TEXT_SELECT_HOME, |t| t.move_home(true)),
+        (TEXT_SELECT_END, |t| t.move_end(true)),
+        (TEXT_SELECT_WORD_LEFT, |t| t.move_word_left(true)),
+        (TEXT_SELECT_WORD_RIGHT, |t| t.move_word_right(true)),
+        (TEXT_CARET_UP, |t| t.move_up(false)),
+        (TEXT_CARET_DOWN, |t| t.move_down(false)),
+        (TEXT_SELECT_UP, |t| t.move_up(true)),
+        (TEXT_SELECT_DOWN, |t| t.move_down(true)),
+    ];
+
+    for (focus, mut input_text, mut editing, repeat) in &mut q {
+        if focus.state() != FocusState::Focused {
+            editing.repeat = None;
+            continue;
+        }
+
+        let repeat = repeat.unwrap_or(&global_repeat);
+
+        // The last pressed key is the one which repeats.
+        for (index, (action, edit)) in actions.iter().enumerate() {
+            if bindings.just_pressed(action, &input_keycode) {
+                edit(&mut input_text);
+                editing.repeat = Some(RepeatState {
+                    action: index,
+                    timer: Timer::from_seconds(repeat.delay, false),
+                });
+            }
+        }
+
+        if let Some(state) = &mut editing.repeat {
+            let (action, edit) = actions[state.action];
+
+            if bindings.pressed(action, &input_keycode) == false {
+                editing.repeat = None;
+            } else if state.timer.tick(time.delta()).just_finished() {
+                edit(&mut input_text);
+                state.timer = Timer::from_seconds(repeat.interval, false);
+            }
+        }
+    }
+}
+
+fn update_text_clipboard(
+    mut q: Query<
+        (
+            &Focusable,
+            &mut InputText,
+            Option<&InputFilter>,
+            Option<&PasswordMask>,
+        ),
+        Without<Disabled>,
+    >,
+    input_keycode: Res<Input<KeyCode>>,
+    bindings: Res<WidgetKeyBindings>,
+    mut clipboard: ResMut<WidgetClipboard>,
+) {
+    for (focus, mut input_text, filter, mask) in &mut q {
+        if focus.state() != FocusState::Focused {
+            continue;
+        }
+
+        let can_copy = input_text.selection().is_some() && mask.is_none();
+
+        if bindings.just_pressed(TEXT_SELECT_ALL, &input_keycode) {
+            input_text.select_all();
+        }
+
+        if bindings.just_pressed(TEXT_COPY, &input_keycode) && can_copy {
+            clipboard.set(input_text.selected_text());
+        }
+
+        if bindings.just_pressed(TEXT_CUT, &input_keycode) && can_copy {
+            clipboard.set(input_text.selected_text());
+            input_text.delete_selection();
+        }
+
+        if bindings.just_pressed(TEXT_PASTE, &input_keycode) {
+            // Line breaks are dropped, unless the text is multiline.
+            if let Some(pasted) = clipboard.get() {
+                let multiline = input_text.multiline;
+                let mut pasted = pasted.replace(
+                    |c: char| c.is_control() && (multiline == false || c != '\n'),
+                    "",
+                );
+                if let Some(filter) = filter {
+                    pasted = filter.filter_insert(&input_text, &pasted);
+                }
+
+                if pasted.is_empty() == false {
+                    input_text.insert_str(&pasted);
+                }
+            }
+        }
+    }
+}
+
+fn update_text_pointer(
+    mut q: Query<
+        (
+            &PointerTarget,
+            &mut InputText,
+            &InputTextMeta,
+            Option<&PasswordMask>,
+        ),
+        Without<Disabled>,
+    >,
+    q_text_nodes: Query<(&Node, &GlobalTransform), With<InputTextDisplayText>>,
+    text_pipeline: Res<DefaultTextPipeline>,
+    windows: Res<Windows>,
+    input_keycode: Res<Input<KeyCode>>,
+    mut down: EventReader<PointerDown>,
+    mut double_click: EventReader<DoubleClick>,
+) {
+    let cursor = match cursor_position(&windows) {
+        Some(cursor) => cursor,
+        None => return,
+    };
+    let scale_factor = windows
+        .get_primary()
+        .map_or(1.0, |window| window.scale_factor() as f32);
+
+    let caret_at = |input_text: &InputText, meta: &InputTextMeta, mask: Option<&PasswordMask>| {
+        let range = input_text
+            .selection()
+            .unwrap_or(input_text.caret..input_text.caret);
+
+        // Glyph positions are in physical pixels, from the left edge of each text node.
+        for (entity, offset, end) in [
+            (meta.text_entity, 0, range.start),
+            (meta.selected_entity, range.start, range.end),
+            (meta.tail_entity, range.end, input_text.text.len()),
+        ] {
+            let (node, transform) = match q_text_nodes.get(entity) {
+                Ok(text_node) => text_node,
+                Err(_) => continue,
             };
+            let left = transform.translation().x - node.size.x / 2.0;
+
+            if let Some(layout) = text_pipeline.get_glyphs(&entity) {
+                for glyph in &layout.glyphs {
+                    if cursor.x < left + glyph.position.x / scale_factor {
+                        return match mask {
+                            // Masked glyphs map to graphemes of the real text.
+                            Some(mask) if mask.is_masked() => input_text.text[offset..end]
+                                .grapheme_indices(true)
+                                .nth(glyph.byte_index / mask.mask.len_utf8())
+                                .map_or(end, |(i, _)| offset + i),
+                            _ => offset + glyph.byte_index,
+                        };
+                    }
+                }
+            }
+        }
+
+        input_text.text.len()
+    };
+
+    for evt in down.iter() {
+        if evt.button != MouseButton::Left {
+            continue;
+        }
+
+        if let Ok((_, mut input_text, meta, mask)) = q.get_mut(evt.entity) {
+            let caret = caret_at(&input_text, meta, mask);
+
+            if input_keycode.any_pressed([KeyCode::LShift, KeyCode::RShift]) {
+                input_text.select_to(caret);
+            } else {
+                input_text.set_caret(caret);
+            }
+        }
+    }
+
+    for evt in double_click.iter() {
+        if let Ok((_, mut input_text, meta, mask)) = q.get_mut(evt.entity) {
+            // Word boundaries would give away where the spaces of a password are.
+            if mask.is_some() {
+                input_text.select_all();
+            } else {
+                let caret = caret_at(&input_text, meta, mask);
+                input_text.select_word_at(caret);
+            }
+        }
+    }
+
+    // Dragging extends the selection from where the pointer went down.
+    for (target, mut input_text, meta, mask) in &mut q {
+        if target.is_pressed() {
+            let caret = caret_at(&input_text, meta, mask);
 
-            if backspace {
-                input_text.text.pop();
+            if caret != input_text.caret {
+                input_text.select_to(caret);
             }
         }
     }
 }
 
 fn update_text_caret(
-    mut q: Query<(&Focusable, &mut InputTextMeta), With<InputText>>,
+    mut q: Query<(&Focusable, &mut InputTextCaret, ChangeTrackers<InputText>)>,
     mut q_caret: Query<&mut Style, With<InputTextDisplayCaret>>,
     time: Res<Time>,
 ) {
-    for (focus, mut meta) in &mut q {
+    for (focus, mut caret, input_tracker) in &mut q {
         if focus.state() == FocusState::Focused {
-            meta.caret_timer.tick(time.delta());
+            caret.timer.tick(time.delta());
 
-            if meta.caret_timer.just_finished() {
+            // Keep the caret solid while editing, blinking only restarts once idle.
+            if input_tracker.is_changed() {
+                caret.timer.reset();
+                caret.visible = false;
+            }
+
+            if input_tracker.is_changed() || caret.timer.just_finished() {
                 let style = &mut q_caret
-                    .get_mut(meta.caret_entity)
+                    .get_mut(caret.entity)
                     .expect("Every InputText should have a caret child");
 
-                meta.caret_visible = !meta.caret_visible;
+                caret.visible = !caret.visible;
 
-                style.display = if meta.caret_visible {
+                style.display = if caret.visible {
                     Display::Flex
                 } else {
                     Display::None
@@ -239,3 +1346,619 @@ fn update_text_caret(
         }
     }
 }
+
+fn update_text_color(
+    q: Query<(Entity, &InputTextMeta, Option<&Disabled>, Option<&Invalid>), With<InputText>>,
+    q_added: Query<Entity, (With<InputText>, Or<(Added<Disabled>, Added<Invalid>)>)>,
+    removed_disabled: RemovedComponents<Disabled>,
+    removed_invalid: RemovedComponents<Invalid>,
+    mut q_text: Query<&mut Text, With<InputTextDisplayText>>,
+    theme: Res<WidgetTheme>,
+) {
+    let changed = if theme.is_changed() {
+        q.iter().map(|(e, ..)| e).collect::<Vec<_>>()
+    } else {
+        q_added
+            .iter()
+            .chain(removed_disabled.iter())
+            .chain(removed_invalid.iter())
+            .collect()
+    };
+
+    for e in changed {
+        if let Ok((_, meta, disabled, invalid)) = q.get(e) {
+            for text_entity in [meta.text_entity, meta.selected_entity, meta.tail_entity] {
+                if let Ok(mut text) = q_text.get_mut(text_entity) {
+                    text.sections[0].style.color = if disabled.is_some() {
+                        theme.disabled_text
+                    } else if invalid.is_some() {
+                        theme.invalid_text
+                    } else {
+                        theme.text
+                    };
+                }
+            }
+        }
+    }
+}
+
+fn validate_text(
+    mut commands: Commands,
+    q: Query<
+        (Entity, &InputText, &InputValidator, Option<&Invalid>),
+        Or<(Changed<InputText>, Changed<InputValidator>)>,
+    >,
+    mut writer: EventWriter<InputValidationChanged>,
+) {
+    for (entity, input_text, validator, invalid) in &q {
+        let valid = (validator.0)(&input_text.text);
+
+        if valid == invalid.is_some() {
+            if valid {
+                commands.entity(entity).remove::<Invalid>();
+            } else {
+                commands.entity(entity).insert(Invalid);
+            }
+
+            writer.send(InputValidationChanged { entity, valid });
+        }
+    }
+}
+
+fn update_placeholder(
+    q: Query<(&InputText, &InputTextMeta, &Focusable, Option<&Placeholder>)>,
+    mut q_placeholder: Query<(&mut Text, &mut Style), Without<InputTextDisplayText>>,
+    theme: Res<WidgetTheme>,
+) {
+    for (input_text, meta, focus, placeholder) in &q {
+        let (mut text, mut style) = match q_placeholder.get_mut(meta.placeholder_entity) {
+            Ok(placeholder_node) => placeholder_node,
+            Err(_) => continue,
+        };
+
+        let display = match placeholder {
+            Some(placeholder)
+                if input_text.text.is_empty()
+                    && (placeholder.show_when_focused || focus.state() != FocusState::Focused) =>
+            {
+                Display::Flex
+            }
+            _ => Display::None,
+        };
+
+        if style.display != display {
+            style.display = display;
+        }
+
+        if let Some(placeholder) = placeholder {
+            if text.sections[0].value != placeholder.text {
+                text.sections[0].value = placeholder.text.clone();
+            }
+        }
+
+        if theme.is_changed() {
+            text.sections[0].style.color = theme.placeholder_text;
+        }
+    }
+}
+
+// Spawns or despawns the reveal button whenever the mask is changed or removed.
+fn update_reveal_button(
+    mut commands: Commands,
+    q: Query<(Entity, &PasswordMask, &InputTextMeta), Changed<PasswordMask>>,
+    q_buttons: Query<(Entity, &PasswordRevealButton)>,
+    removed: RemovedComponents<PasswordMask>,
+    asset_server: Res<AssetServer>,
+) {
+    let button_of = |entity: Entity| {
+        q_buttons
+            .iter()
+            .find(|(_, PasswordRevealButton(field))| *field == entity)
+            .map(|(button, _)| button)
+    };
+
+    for entity in removed.iter() {
+        if let Some(button) = button_of(entity) {
+            commands.entity(button).despawn_recursive();
+        }
+    }
+
+    for (entity, mask, meta) in &q {
+        if let Some(button) = button_of(entity) {
+            if mask.reveal_button == false {
+                commands.entity(button).despawn_recursive();
+            }
+            continue;
+        }
+
+        if mask.reveal_button == false {
+            continue;
+        }
+
+        let button = TextButton::build(
+            StringLabel::from(reveal_label(mask)),
+            &mut commands,
+            &asset_server,
+        );
+
+        // Clicking it must not take the focus away from the field.
+        commands
+            .entity(button)
+            .remove::<Focusable>()
+            .insert(Style {
+                size: Size::new(Val::Px(REVEAL_BUTTON_WIDTH), Val::Percent(100.0)),
+                // Kept over the right edge, so long text scrolls beneath it.
+                position_type: PositionType::Absolute,
+                position: UiRect::new(Val::Undefined, Val::Px(0.0), Val::Undefined, Val::Undefined),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            })
+            .insert(Toggled(mask.revealed))
+            .insert(PasswordRevealButton(entity));
+
+        commands.entity(meta.content_entity).add_child(button);
+    }
+}
+
+fn toggle_password_reveal(
+    mut reader: EventReader<ButtonToggled>,
+    q_buttons: Query<&PasswordRevealButton>,
+    mut q_masks: Query<&mut PasswordMask>,
+) {
+    for &ButtonToggled(button, revealed) in reader.iter() {
+        if let Ok(PasswordRevealButton(entity)) = q_buttons.get(button) {
+            if let Ok(mut mask) = q_masks.get_mut(*entity) {
+                mask.revealed = revealed;
+            }
+        }
+    }
+}
+
+fn update_reveal_button_label(
+    q_masks: Query<&PasswordMask, Changed<PasswordMask>>,
+    mut q_buttons: Query<(&PasswordRevealButton, &mut ButtonLabel)>,
+) {
+    for (PasswordRevealButton(entity), mut label) in &mut q_buttons {
+        if let Ok(mask) = q_masks.get(*entity) {
+            if label.0 != reveal_label(mask) {
+                label.0 = reveal_label(mask).to_string();
+            }
+        }
+    }
+}
+
+fn reveal_label(mask: &PasswordMask) -> &'static str {
+    if mask.revealed {
+        "Hide"
+    } else {
+        "Show"
+    }
+}
+
+// Caret and selection changes also trigger `Changed<InputText>`, so compare the text itself.
+fn dispatch_text_changed(
+    mut q: Query<(Entity, &InputText, &mut InputTextEditing, &Focusable), Changed<InputText>>,
+    mut writer: EventWriter<TextChanged>,
+) {
+    for (entity, input_text, mut editing, focus) in &mut q {
+        if input_text.text == editing.reported_text {
+            continue;
+        }
+
+        editing.reported_text = input_text.text.clone();
+
+        if focus.state() == FocusState::Focused {
+            writer.send(TextChanged {
+                entity,
+                text: input_text.text.clone(),
+            });
+        }
+    }
+}
+
+fn submit_text(
+    mut q: Query<(Entity, &mut InputText, &Focusable, Option<&ClearOnSubmit>), Without<Disabled>>,
+    input_keycode: Res<Input<KeyCode>>,
+    bindings: Res<WidgetKeyBindings>,
+    mut writer: EventWriter<TextSubmitted>,
+) {
+    if bindings.just_pressed(TEXT_SUBMIT, &input_keycode) == false {
+        return;
+    }
+
+    for (entity, mut input_text, focus, clear) in &mut q {
+        // Enter breaks the line on multiline text instead.
+        if focus.state() != FocusState::Focused || input_text.multiline {
+            continue;
+        }
+
+        let text = if clear.is_some() {
+            input_text.take()
+        } else {
+            input_text.text.clone()
+        };
+
+        writer.send(TextSubmitted { entity, text });
+    }
+}
+
+// Layout is one frame behind, so the scroll is corrected on the frame after the caret moves.
+fn scroll_to_caret(
+    mut q: Query<(&mut InputTextMeta, Option<&PasswordMask>), With<InputText>>,
+    q_nodes: Query<(&Node, &GlobalTransform)>,
+    q_selection: Query<&Style, With<InputTextDisplaySelection>>,
+    mut q_scroll: Query<&mut Style, (With<InputTextScroll>, Without<InputTextDisplaySelection>)>,
+) {
+    for (mut meta, mask) in &mut q {
+        let (view_node, view_transform) = match q_nodes.get(meta.content_entity) {
+            Ok(view) => view,
+            Err(_) => continue,
+        };
+        let (selection_node, selection_transform) = match q_nodes.get(meta.selection_entity) {
+            Ok(selection) => selection,
+            Err(_) => continue,
+        };
+        let (scroll_node, _) = match q_nodes.get(meta.scroll_entity) {
+            Ok(scroll) => scroll,
+            Err(_) => continue,
+        };
+
+        let view_left = view_transform.translation().x - view_node.size.x / 2.0 + SCROLL_MARGIN;
+        let mut view_right = view_left + view_node.size.x - SCROLL_MARGIN * 2.0;
+        // Text beneath the reveal button is hidden by it.
+        if mask.map_or(false, |mask| mask.reveal_button) {
+            view_right -= REVEAL_BUTTON_WIDTH;
+        }
+
+        // The caret sits at the start of the selection node when it's reversed, or at the end.
+        let selection_left = selection_transform.translation().x - selection_node.size.x / 2.0;
+        let caret_x = match q_selection.get(meta.selection_entity) {
+            Ok(style) if style.flex_direction == FlexDirection::RowReverse => selection_left,
+            _ => selection_left + selection_node.size.x,
+        };
+
+        let mut scroll = meta.scroll;
+        if caret_x < view_left {
+            scroll -= view_left - caret_x;
+        } else if caret_x > view_right {
+            scroll += caret_x - view_right;
+        }
+
+        // Don't leave empty room on the right after deleting text.
+        let max_scroll = (scroll_node.size.x - (view_right - view_left)).max(0.0);
+        scroll = scroll.clamp(0.0, max_scroll);
+
+        if scroll != meta.scroll {
+            meta.scroll = scroll;
+
+            if let Ok(mut style) = q_scroll.get_mut(meta.scroll_entity) {
+                style.position.left = Val::Px(-scroll);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clipboard::{Clipboard, MemoryClipboard};
+
+    fn input(text: &str) -> InputText {
+        let mut input = InputText::default();
+        input.insert_str(text);
+        input
+    }
+
+    #[test]
+    fn select_all_selects_whole_text() {
+        let mut input = input("hello world");
+        input.select_all();
+
+        assert_eq!(input.selection(), Some(0..11));
+        assert_eq!(input.selected_text(), "hello world");
+    }
+
+    #[test]
+    fn empty_selection_is_none() {
+        let mut input = input("hello");
+        input.set_caret(2);
+        input.select_to(2);
+
+        assert_eq!(input.selection(), None);
+        assert_eq!(input.selected_text(), "");
+    }
+
+    #[test]
+    fn selection_extends_from_anchor() {
+        let mut input = input("hello world");
+        input.set_caret(8);
+        input.move_word_left(true);
+        input.move_left(true);
+
+        assert_eq!(input.selection(), Some(5..8));
+        assert_eq!(input.selected_text(), " wo");
+    }
+
+    #[test]
+    fn moving_without_select_collapses_selection() {
+        let mut input = input("hello world");
+        input.set_caret(2);
+        input.select_to(7);
+        input.move_left(false);
+
+        assert_eq!(input.selection(), None);
+        assert_eq!(input.caret(), 2);
+    }
+
+    #[test]
+    fn select_word_at_stops_at_whitespace() {
+        let mut input = input("copy this text");
+        input.select_word_at(6);
+
+        assert_eq!(input.selected_text(), "this");
+    }
+
+    #[test]
+    fn typing_replaces_selection() {
+        let mut input = input("hello world");
+        input.select_word_at(8);
+        input.insert_str("there");
+
+        assert_eq!(input.text(), "hello there");
+        assert_eq!(input.caret(), 11);
+    }
+
+    #[test]
+    fn cut_and_paste_moves_text() {
+        let mut clipboard = MemoryClipboard::default();
+        let mut input = input("one two");
+        input.select_word_at(0);
+
+        clipboard.set(input.selected_text().to_string());
+        assert!(input.delete_selection());
+        assert_eq!(input.text(), " two");
+
+        input.move_end(false);
+        input.insert_str(&clipboard.get().unwrap());
+        assert_eq!(input.text(), " twoone");
+    }
+
+    #[test]
+    fn delete_selection_without_selection_does_nothing() {
+        let mut input = input("hello");
+        assert_eq!(input.delete_selection(), false);
+        assert_eq!(input.text(), "hello");
+    }
+
+    #[test]
+    fn delete_back_removes_whole_grapheme() {
+        let mut input = input("cafe\u{301}");
+        input.delete_back();
+
+        assert_eq!(input.text(), "caf");
+        assert_eq!(input.caret(), 3);
+    }
+
+    #[test]
+    fn delete_forward_removes_whole_grapheme() {
+        let mut input = input("a\u{1F44D}\u{1F3FD}b");
+        input.set_caret(1);
+        input.delete_forward();
+
+        assert_eq!(input.text(), "ab");
+    }
+
+    #[test]
+    fn caret_moves_over_graphemes() {
+        let flag = "\u{1F1EB}\u{1F1F7}";
+        let mut input = input(&format!("a{flag}b"));
+        input.set_caret(1);
+
+        input.move_right(false);
+        assert_eq!(input.caret(), 1 + flag.len());
+        input.move_left(false);
+        assert_eq!(input.caret(), 1);
+    }
+
+    #[test]
+    fn set_caret_rounds_down_to_grapheme() {
+        let mut input = input("e\u{301}x");
+        input.set_caret(2);
+
+        assert_eq!(input.caret(), 0);
+    }
+
+    #[test]
+    fn delete_word_back_skips_trailing_whitespace() {
+        let mut input = input("say hello  ");
+        input.delete_word_back();
+
+        assert_eq!(input.text(), "say ");
+    }
+
+    fn type_text(input: &mut InputText, text: &str) {
+        for c in text.chars() {
+            input.insert(c);
+        }
+    }
+
+    #[test]
+    fn typed_word_is_undone_at_once() {
+        let mut input = InputText::default();
+        type_text(&mut input, "hello");
+
+        assert!(input.undo());
+        assert_eq!(input.text(), "");
+        assert_eq!(input.undo(), false);
+    }
+
+    #[test]
+    fn whitespace_starts_new_undo_entry() {
+        let mut input = InputText::default();
+        type_text(&mut input, "hi there");
+
+        input.undo();
+        assert_eq!(input.text(), "hi ");
+        input.undo();
+        assert_eq!(input.text(), "hi");
+        input.undo();
+        assert_eq!(input.text(), "");
+    }
+
+    #[test]
+    fn moving_caret_ends_typing_run() {
+        let mut input = InputText::default();
+        type_text(&mut input, "ab");
+        input.move_left(false);
+        type_text(&mut input, "c");
+        assert_eq!(input.text(), "acb");
+
+        input.undo();
+        assert_eq!(input.text(), "ab");
+        assert_eq!(input.caret(), 1);
+    }
+
+    #[test]
+    fn deletions_are_undone_one_by_one() {
+        let mut input = InputText::default();
+        type_text(&mut input, "abc");
+        input.delete_back();
+        input.delete_back();
+
+        input.undo();
+        assert_eq!(input.text(), "ab");
+        input.undo();
+        assert_eq!(input.text(), "abc");
+    }
+
+    #[test]
+    fn redo_restores_undone_edit() {
+        let mut input = InputText::default();
+        type_text(&mut input, "abc");
+        input.undo();
+
+        assert!(input.redo());
+        assert_eq!(input.text(), "abc");
+        assert_eq!(input.caret(), 3);
+        assert_eq!(input.redo(), false);
+    }
+
+    #[test]
+    fn new_edit_clears_redo() {
+        let mut input = InputText::default();
+        type_text(&mut input, "abc");
+        input.undo();
+        type_text(&mut input, "x");
+
+        assert_eq!(input.redo(), false);
+        assert_eq!(input.text(), "x");
+    }
+
+    #[test]
+    fn typing_after_undo_starts_new_entry() {
+        let mut input = InputText::default();
+        type_text(&mut input, "ab");
+        input.insert_str(" cd");
+        input.undo();
+        type_text(&mut input, "x");
+
+        input.undo();
+        assert_eq!(input.text(), "ab");
+    }
+
+    #[test]
+    fn history_is_limited_to_depth() {
+        let mut input = InputText::default();
+        input.set_history_depth(2);
+        input.insert_str("a");
+        input.insert_str("b");
+        input.insert_str("c");
+
+        assert!(input.undo());
+        assert!(input.undo());
+        assert_eq!(input.undo(), false);
+        assert_eq!(input.text(), "a");
+    }
+
+    #[test]
+    fn filter_drops_chars_outside_classes() {
+        let filter = InputFilter::default().with_classes([CharClass::Numeric]);
+        let input = InputText::default();
+
+        assert_eq!(filter.filter_insert(&input, "a1b2"), "12");
+    }
+
+    #[test]
+    fn filter_applies_predicate() {
+        let filter = InputFilter::default().with_predicate(|c| c != '_');
+        let input = InputText::default();
+
+        assert_eq!(filter.filter_insert(&input, "a_b"), "ab");
+    }
+
+    #[test]
+    fn filter_pattern_checks_each_char() {
+        let filter = InputFilter::default().with_pattern("[0-9a-f]").unwrap();
+        let input = input("c0ff");
+
+        assert_eq!(filter.filter_insert(&input, "e!g"), "e");
+    }
+
+    #[test]
+    fn filter_pattern_accepts_partial_input() {
+        // The whole text doesn't match yet, but typing must still be possible.
+        let filter = InputFilter::default().with_pattern(r"[\d-]").unwrap();
+        let input = input("555");
+
+        assert_eq!(filter.filter_insert(&input, "-"), "-");
+    }
+
+    #[test]
+    fn filter_max_length_counts_graphemes() {
+        let filter = InputFilter::default().with_max_length(4);
+        let input = input("e\u{301}a");
+
+        assert_eq!(filter.filter_insert(&input, "bcd"), "bc");
+    }
+
+    #[test]
+    fn filter_max_length_frees_selected_text() {
+        let filter = InputFilter::default().with_max_length(3);
+        let mut input = input("abc");
+        input.select_all();
+
+        assert_eq!(filter.filter_insert(&input, "wxyz"), "wxy");
+    }
+
+    #[test]
+    fn validator_pattern_matches_whole_text() {
+        let validator = InputValidator::pattern(r"\d{3}-\d{4}").unwrap();
+
+        assert!((validator.0)("555-1234"));
+        assert_eq!((validator.0)("555-12345"), false);
+        assert_eq!((validator.0)("x555-1234"), false);
+    }
+
+    #[test]
+    fn set_text_clears_history() {
+        let mut input = InputText::default();
+        type_text(&mut input, "typed");
+        input.set_text("set by code");
+
+        assert_eq!(input.undo(), false);
+        assert_eq!(input.text(), "set by code");
+        assert_eq!(input.caret(), 11);
+    }
+
+    #[test]
+    fn taken_text_can_be_undone() {
+        let mut input = InputText::default();
+        type_text(&mut input, "command");
+
+        assert_eq!(input.take(), "command");
+        assert_eq!(input.text(), "");
+        input.undo();
+        assert_eq!(input.text(), "command");
+    }
+}
@@ -1,8 +1,19 @@
 use bevy::{prelude::*, ui::FocusPolicy};
+use bevy_ui_navigation::prelude::{FocusState, Focusable};
 
-use crate::widget::{Widget, WidgetLabel};
+use crate::{
+    accessibility::{AccessibilityNode, AccessibilityRole},
+    key_bindings::{WidgetKeyBindings, LIST_PAGE_DOWN, LIST_PAGE_UP},
+    pointer::{cursor_position, relative_cursor_position, Click, PointerTarget},
+    theme::WidgetTheme,
+    widget::{Disabled, Widget, WidgetEvent, WidgetFrames, WidgetLabel},
+};
 
 const ITEM_HEIGHT: f32 = 20.0;
+const ITEM_COLOR: Color = Color::rgb(0.6, 0.6, 0.6);
+// Border of the list and of its container, on each side.
+const LIST_BORDER: f32 = 2.0;
+const CONTAINER_BORDER: f32 = 5.0;
 
 pub(super) struct ItemListPlugin;
 
@@ -10,8 +21,12 @@ impl Plugin for ItemListPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<ItemList>()
             .register_type::<ItemIndex>()
+            .add_event::<ItemClicked>()
             .add_system(update_item_list_items)
-            .add_system(update_item_list_max_visible_items);
+            .add_system(update_item_list_max_visible_items)
+            .add_system(restyle_disabled_item_lists)
+            .add_system(scroll_item_list_pages)
+            .add_system(dispatch_item_clicks);
     }
 }
 
@@ -19,17 +34,16 @@ impl Plugin for ItemListPlugin {
 struct ItemListMeta {
     container_entity: Entity,
     item_font: Handle<Font>,
-    max_visible_items: usize,
 }
 
 impl ItemListMeta {
-    fn create_item_bundle(&self, content: String) -> TextBundle {
+    fn create_item_bundle(&self, content: String, color: Color) -> TextBundle {
         TextBundle::from_section(
             content,
             TextStyle {
                 font: self.item_font.clone(),
                 font_size: 15.0,
-                color: Color::rgb(0.6, 0.6, 0.6).into(),
+                color,
             },
         )
         .with_style(Style {
@@ -48,11 +62,52 @@ struct ItemIndex(usize);
 #[reflect(Component)]
 pub struct ItemList {
     pub items: Vec<String>,
+    // Index of the item drawn with the highlight color.
+    pub highlighted: Option<usize>,
+    // How many of the most recent items are skipped.
+    scroll: usize,
+    // How many items fit in the list, which is also the size of a page.
+    max_visible_items: usize,
+}
+
+impl ItemList {
+    // Height of a list showing exactly `items` items.
+    pub fn height_for(items: usize) -> f32 {
+        items as f32 * ITEM_HEIGHT + (LIST_BORDER + CONTAINER_BORDER) * 2.0
+    }
+
+    pub fn scroll(&self) -> usize {
+        self.scroll
+    }
+
+    pub fn scroll_by(&mut self, delta: isize) {
+        self.scroll = (self.scroll as isize + delta).clamp(0, self.max_scroll() as isize) as usize;
+    }
+
+    // Scrolling stops once the oldest item is shown, so the last page is always full.
+    fn max_scroll(&self) -> usize {
+        self.items
+            .len()
+            .saturating_sub(self.max_visible_items.max(1))
+    }
 }
 
 #[derive(Component)]
 struct ItemListContainer;
 
+// Sent when an item of the list is clicked.
+pub struct ItemClicked {
+    pub entity: Entity,
+    // Index of the clicked item on `ItemList::items`.
+    pub index: usize,
+}
+
+impl WidgetEvent for ItemClicked {
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+}
+
 impl Widget for ItemList {
     fn build<L: WidgetLabel>(
         label: L,
@@ -63,7 +118,7 @@ impl Widget for ItemList {
             .spawn_bundle(NodeBundle {
                 style: Style {
                     size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
-                    border: UiRect::all(Val::Px(5.0)),
+                    border: UiRect::all(Val::Px(CONTAINER_BORDER)),
                     flex_direction: FlexDirection::Column,
                     flex_shrink: 0.0,
                     ..default()
@@ -75,26 +130,28 @@ impl Widget for ItemList {
             .insert(ItemListContainer)
             .id();
 
-        commands
-            .spawn_bundle(NodeBundle {
-                style: Style {
-                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
-                    border: UiRect::all(Val::Px(2.0)),
-                    flex_direction: FlexDirection::Column,
-                    ..default()
-                },
-                focus_policy: FocusPolicy::Pass,
-                color: Color::rgba(0.5, 0.5, 0.5, 0.1).into(),
+        let mut list = commands.spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                border: UiRect::all(Val::Px(LIST_BORDER)),
+                flex_direction: FlexDirection::Column,
                 ..default()
-            })
-            .add_child(list_bg)
+            },
+            focus_policy: FocusPolicy::Pass,
+            color: Color::rgba(0.5, 0.5, 0.5, 0.1).into(),
+            ..default()
+        });
+        let list_entity = list.id();
+
+        list.add_child(list_bg)
+            .insert(WidgetFrames(vec![list_entity, list_bg]))
             .insert(Name::new(label.name()))
+            .insert(AccessibilityNode::new(AccessibilityRole::List).with_name(label.name()))
             .insert(label)
             .insert(ItemList::default())
             .insert(ItemListMeta {
                 container_entity: list_bg,
                 item_font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                max_visible_items: 0,
             })
             .id()
     }
@@ -102,28 +159,57 @@ impl Widget for ItemList {
 
 fn update_item_list_items(
     mut commands: Commands,
-    q: Query<(&ItemList, &ItemListMeta), (With<ItemListMeta>, Changed<ItemList>)>,
+    q: Query<(&ItemList, &ItemListMeta, Option<&Disabled>), Changed<ItemList>>,
     q_containers: Query<&Children, With<ItemListContainer>>,
-    mut q_items: Query<(Entity, &mut Text), With<ItemIndex>>,
+    mut q_items: Query<(Entity, &mut Text, &mut AccessibilityNode), With<ItemIndex>>,
+    theme: Res<WidgetTheme>,
 ) {
-    for (item_list, meta) in &q {
+    for (item_list, meta, disabled) in &q {
         let children = q_containers.get(meta.container_entity).ok();
 
+        let visible_items = item_list
+            .items
+            .len()
+            .saturating_sub(item_list.scroll)
+            .min(item_list.max_visible_items);
+
         // Sync children with item list items
-        for (index, item) in item_list.items.iter().rev().enumerate() {
-            if index >= meta.max_visible_items {
+        for (index, item) in item_list
+            .items
+            .iter()
+            .rev()
+            .skip(item_list.scroll)
+            .enumerate()
+        {
+            if index >= item_list.max_visible_items {
                 break;
             }
 
+            let item_index = item_list.items.len() - 1 - item_list.scroll - index;
+            let color = if disabled.is_some() {
+                theme.disabled_text
+            } else if item_list.highlighted == Some(item_index) {
+                theme.highlighted_item
+            } else {
+                ITEM_COLOR
+            };
+
             let item_entity = if let Some(children) = children && index < children.len() {
-                let (entity, mut text) = q_items
+                let (entity, mut text, mut node) = q_items
                     .get_mut(children[index])
                     .expect("Child item should exists");
                 text.sections[0].value = item.clone();
+                text.sections[0].style.color = color;
+                node.name = Some(item.clone());
                 entity
             } else {
                 let item = commands
-                    .spawn_bundle(meta.create_item_bundle(item.clone()))
+                    .spawn_bundle(meta.create_item_bundle(item.clone(), color))
+                    .insert(
+                        AccessibilityNode::new(AccessibilityRole::ListItem).with_name(item.clone()),
+                    )
+                    .insert(Interaction::default())
+                    .insert(PointerTarget::default())
                     .id();
                 commands.entity(meta.container_entity).add_child(item);
                 item
@@ -137,20 +223,145 @@ fn update_item_list_items(
 
         // Remove unused children
         if let Some(children) = children {
-            for i in (item_list.items.len() - 1)..children.len() {
-                commands.entity(children[i]).despawn();
+            for &child in children.iter().skip(visible_items) {
+                commands.entity(child).despawn();
             }
         }
     }
 }
 
 fn update_item_list_max_visible_items(
-    mut q: Query<&mut ItemListMeta, (With<ItemList>, Changed<Node>)>,
+    mut q: Query<(&mut ItemList, &ItemListMeta), Changed<Node>>,
     q_containers: Query<&Node, With<ItemListContainer>>,
 ) {
-    for mut meta in &mut q {
+    for (mut item_list, meta) in &mut q {
         if let Ok(container_node) = q_containers.get(meta.container_entity) {
-            meta.max_visible_items = (container_node.size.y / ITEM_HEIGHT) as usize;
+            let max_visible_items = (container_node.size.y / ITEM_HEIGHT) as usize;
+
+            // Items are only synced when the list changes, which this assignment marks.
+            if item_list.max_visible_items != max_visible_items {
+                item_list.max_visible_items = max_visible_items;
+            }
+        }
+    }
+}
+
+// Items are only synced when the list changes, so touch it to restyle them.
+fn restyle_disabled_item_lists(
+    mut q: Query<&mut ItemList>,
+    q_added: Query<Entity, (With<ItemList>, Added<Disabled>)>,
+    removed: RemovedComponents<Disabled>,
+    theme: Res<WidgetTheme>,
+) {
+    if theme.is_changed() {
+        for mut item_list in &mut q {
+            item_list.set_changed();
+        }
+        return;
+    }
+
+    for entity in q_added.iter().chain(removed.iter()) {
+        if let Ok(mut item_list) = q.get_mut(entity) {
+            item_list.set_changed();
+        }
+    }
+}
+
+// Pages the list under the cursor or, if there is none, the list next to the focused widget,
+// like the log of the console while typing a command.
+fn scroll_item_list_pages(
+    mut q: Query<
+        (
+            Entity,
+            &mut ItemList,
+            &Node,
+            &GlobalTransform,
+            &ComputedVisibility,
+            Option<&Parent>,
+        ),
+        Without<Disabled>,
+    >,
+    q_focusables: Query<(&Focusable, Option<&Parent>)>,
+    input: Res<Input<KeyCode>>,
+    bindings: Res<WidgetKeyBindings>,
+    windows: Res<Windows>,
+) {
+    let direction = if bindings.just_pressed(LIST_PAGE_UP, &input) {
+        1
+    } else if bindings.just_pressed(LIST_PAGE_DOWN, &input) {
+        -1
+    } else {
+        return;
+    };
+
+    let cursor = cursor_position(&windows);
+    let focused_parent = q_focusables
+        .iter()
+        .find(|(focusable, _)| focusable.state() == FocusState::Focused)
+        .and_then(|(_, parent)| parent.map(|parent| parent.get()));
+
+    let mut hovered = None;
+    let mut next_to_focused = None;
+    for (e, _, node, transform, visibility, parent) in &q {
+        if visibility.is_visible() == false {
+            continue;
+        }
+
+        let is_hovered = cursor.map_or(false, |cursor| {
+            let position = relative_cursor_position(cursor, node, transform);
+            position.cmpge(Vec2::ZERO).all() && position.cmplt(node.size).all()
+        });
+
+        if is_hovered {
+            hovered = Some(e);
+        } else if focused_parent.is_some() && parent.map(|parent| parent.get()) == focused_parent {
+            next_to_focused = Some(e);
+        }
+    }
+
+    // Lists showing all their items, like the suggestions of a field, are never scrolled.
+    if let Some(e) = hovered.or(next_to_focused) {
+        let (_, mut item_list, ..) = q.get_mut(e).expect("List should exist");
+        let page = item_list.max_visible_items.max(1) as isize;
+        let scroll = (item_list.scroll as isize + direction * page)
+            .clamp(0, item_list.max_scroll() as isize) as usize;
+
+        if scroll != item_list.scroll {
+            item_list.scroll = scroll;
+        }
+    }
+}
+
+fn dispatch_item_clicks(
+    q: Query<&ItemList, Without<Disabled>>,
+    q_items: Query<(&ItemIndex, &Parent)>,
+    q_containers: Query<&Parent, With<ItemListContainer>>,
+    mut clicks: EventReader<Click>,
+    mut writer: EventWriter<ItemClicked>,
+) {
+    for evt in clicks.iter() {
+        let (item_index, container) = match q_items.get(evt.entity) {
+            Ok(item) => item,
+            Err(_) => continue,
+        };
+        let list = match q_containers.get(container.get()) {
+            Ok(list) => list.get(),
+            Err(_) => continue,
+        };
+
+        if let Ok(item_list) = q.get(list) {
+            // Items are shown from the most recent one, after skipping the scrolled ones.
+            let index = item_list
+                .items
+                .len()
+                .checked_sub(1 + item_list.scroll + item_index.0);
+
+            if let Some(index) = index {
+                writer.send(ItemClicked {
+                    entity: list,
+                    index,
+                });
+            }
         }
     }
 }
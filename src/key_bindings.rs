@@ -0,0 +1,540 @@
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub const CONSOLE_TOGGLE: &str = "console.toggle";
+pub const TEXT_SUBMIT: &str = "text.submit";
+pub const TEXT_DELETE_BACK: &str = "text.delete_back";
+pub const TEXT_DELETE_FORWARD: &str = "text.delete_forward";
+pub const TEXT_DELETE_WORD_BACK: &str = "text.delete_word_back";
+pub const TEXT_CARET_LEFT: &str = "text.caret_left";
+pub const TEXT_CARET_RIGHT: &str = "text.caret_right";
+pub const TEXT_CARET_HOME: &str = "text.caret_home";
+pub const TEXT_CARET_END: &str = "text.caret_end";
+pub const TEXT_CARET_UP: &str = "text.caret_up";
+pub const TEXT_CARET_DOWN: &str = "text.caret_down";
+pub const TEXT_WORD_LEFT: &str = "text.word_left";
+pub const TEXT_WORD_RIGHT: &str = "text.word_right";
+pub const TEXT_SELECT_LEFT: &str = "text.select_left";
+pub const TEXT_SELECT_RIGHT: &str = "text.select_right";
+pub const TEXT_SELECT_HOME: &str = "text.select_home";
+pub const TEXT_SELECT_END: &str = "text.select_end";
+pub const TEXT_SELECT_UP: &str = "text.select_up";
+pub const TEXT_SELECT_DOWN: &str = "text.select_down";
+pub const TEXT_NEWLINE: &str = "text.newline";
+pub const TEXT_SELECT_WORD_LEFT: &str = "text.select_word_left";
+pub const TEXT_SELECT_WORD_RIGHT: &str = "text.select_word_right";
+pub const TEXT_SELECT_ALL: &str = "text.select_all";
+pub const TEXT_COPY: &str = "text.copy";
+pub const TEXT_CUT: &str = "text.cut";
+pub const TEXT_PASTE: &str = "text.paste";
+pub const TEXT_UNDO: &str = "text.undo";
+pub const TEXT_REDO: &str = "text.redo";
+pub const LIST_PAGE_UP: &str = "list.page_up";
+pub const LIST_PAGE_DOWN: &str = "list.page_down";
+pub const SUGGESTION_NEXT: &str = "suggestion.next";
+pub const SUGGESTION_PREVIOUS: &str = "suggestion.previous";
+pub const SUGGESTION_ACCEPT: &str = "suggestion.accept";
+pub const SUGGESTION_DISMISS: &str = "suggestion.dismiss";
+pub const BUTTON_ACTIVATE: &str = "button.activate";
+
+// A key pressed together with an exact set of modifiers.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct KeyChord {
+    pub key: KeyCode,
+    #[serde(default)]
+    pub ctrl: bool,
+    #[serde(default)]
+    pub alt: bool,
+    #[serde(default)]
+    pub shift: bool,
+}
+
+impl KeyChord {
+    pub fn new(key: KeyCode) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            alt: false,
+            shift: false,
+        }
+    }
+
+    pub fn ctrl(mut self) -> Self {
+        self.ctrl = true;
+        self
+    }
+
+    pub fn alt(mut self) -> Self {
+        self.alt = true;
+        self
+    }
+
+    pub fn shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+
+    pub fn modifiers_pressed(&self, input: &Input<KeyCode>) -> bool {
+        self.ctrl == input.any_pressed([KeyCode::LControl, KeyCode::RControl])
+            && self.alt == input.any_pressed([KeyCode::LAlt, KeyCode::RAlt])
+            && self.shift == input.any_pressed([KeyCode::LShift, KeyCode::RShift])
+    }
+
+    pub fn just_pressed(&self, input: &Input<KeyCode>) -> bool {
+        input.just_pressed(self.key) && self.modifiers_pressed(input)
+    }
+
+    pub fn pressed(&self, input: &Input<KeyCode>) -> bool {
+        input.pressed(self.key) && self.modifiers_pressed(input)
+    }
+
+    // Whether pressing the chord can also be received as a typed character.
+    pub fn types_text(&self) -> bool {
+        if self.ctrl || self.alt {
+            return true;
+        }
+
+        matches!(
+            self.key,
+            KeyCode::Return
+                | KeyCode::NumpadEnter
+                | KeyCode::Back
+                | KeyCode::Delete
+                | KeyCode::Insert
+                | KeyCode::Left
+                | KeyCode::Right
+                | KeyCode::Up
+                | KeyCode::Down
+                | KeyCode::Home
+                | KeyCode::End
+                | KeyCode::PageUp
+                | KeyCode::PageDown
+                | KeyCode::Tab
+                | KeyCode::Escape
+        ) == false
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        write!(f, "{:?}", self.key)
+    }
+}
+
+pub(crate) fn key_code_for_char(c: char) -> Option<KeyCode> {
+    let key = match c.to_ascii_uppercase() {
+        'A' => KeyCode::A,
+        'B' => KeyCode::B,
+        'C' => KeyCode::C,
+        'D' => KeyCode::D,
+        'E' => KeyCode::E,
+        'F' => KeyCode::F,
+        'G' => KeyCode::G,
+        'H' => KeyCode::H,
+        'I' => KeyCode::I,
+        'J' => KeyCode::J,
+        'K' => KeyCode::K,
+        'L' => KeyCode::L,
+        'M' => KeyCode::M,
+        'N' => KeyCode::N,
+        'O' => KeyCode::O,
+        'P' => KeyCode::P,
+        'Q' => KeyCode::Q,
+        'R' => KeyCode::R,
+        'S' => KeyCode::S,
+        'T' => KeyCode::T,
+        'U' => KeyCode::U,
+        'V' => KeyCode::V,
+        'W' => KeyCode::W,
+        'X' => KeyCode::X,
+        'Y' => KeyCode::Y,
+        'Z' => KeyCode::Z,
+        '0' => KeyCode::Key0,
+        '1' => KeyCode::Key1,
+        '2' => KeyCode::Key2,
+        '3' => KeyCode::Key3,
+        '4' => KeyCode::Key4,
+        '5' => KeyCode::Key5,
+        '6' => KeyCode::Key6,
+        '7' => KeyCode::Key7,
+        '8' => KeyCode::Key8,
+        '9' => KeyCode::Key9,
+        ' ' => KeyCode::Space,
+        _ => return None,
+    };
+
+    Some(key)
+}
+
+// How held editing keys repeat, in seconds. Used as a resource for every field, or as a
+// component to override it on a single one.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub struct KeyRepeat {
+    // Time between the first press and the first repeat.
+    pub delay: f32,
+    pub interval: f32,
+}
+
+impl Default for KeyRepeat {
+    fn default() -> Self {
+        Self {
+            delay: 0.4,
+            interval: 0.05,
+        }
+    }
+}
+
+// Maps named widget actions to the key chords which trigger them. Every widget system checks
+// its keys through this resource, so it can be changed at runtime or loaded from a file.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WidgetKeyBindings {
+    bindings: BTreeMap<String, Vec<KeyChord>>,
+}
+
+impl Default for WidgetKeyBindings {
+    fn default() -> Self {
+        let mut bindings = Self {
+            bindings: BTreeMap::new(),
+        };
+
+        // Grave and Apostrophe move around with the layout, F1 is there on every keyboard.
+        bindings.bind(
+            CONSOLE_TOGGLE,
+            vec![
+                KeyChord::new(KeyCode::F1),
+                KeyChord::new(KeyCode::Grave).ctrl(),
+                KeyChord::new(KeyCode::Apostrophe).ctrl(),
+            ],
+        );
+        bindings.bind(
+            TEXT_SUBMIT,
+            vec![
+                KeyChord::new(KeyCode::Return),
+                KeyChord::new(KeyCode::NumpadEnter),
+            ],
+        );
+        bindings.bind(TEXT_DELETE_BACK, vec![KeyChord::new(KeyCode::Back)]);
+        bindings.bind(TEXT_DELETE_FORWARD, vec![KeyChord::new(KeyCode::Delete)]);
+        bindings.bind(
+            TEXT_DELETE_WORD_BACK,
+            vec![KeyChord::new(KeyCode::Back).ctrl()],
+        );
+        bindings.bind(TEXT_CARET_LEFT, vec![KeyChord::new(KeyCode::Left)]);
+        bindings.bind(TEXT_CARET_RIGHT, vec![KeyChord::new(KeyCode::Right)]);
+        bindings.bind(TEXT_CARET_HOME, vec![KeyChord::new(KeyCode::Home)]);
+        bindings.bind(TEXT_CARET_END, vec![KeyChord::new(KeyCode::End)]);
+        bindings.bind(TEXT_CARET_UP, vec![KeyChord::new(KeyCode::Up)]);
+        bindings.bind(TEXT_CARET_DOWN, vec![KeyChord::new(KeyCode::Down)]);
+        bindings.bind(TEXT_WORD_LEFT, vec![KeyChord::new(KeyCode::Left).ctrl()]);
+        bindings.bind(TEXT_WORD_RIGHT, vec![KeyChord::new(KeyCode::Right).ctrl()]);
+        bindings.bind(TEXT_SELECT_LEFT, vec![KeyChord::new(KeyCode::Left).shift()]);
+        bindings.bind(
+            TEXT_SELECT_RIGHT,
+            vec![KeyChord::new(KeyCode::Right).shift()],
+        );
+        bindings.bind(TEXT_SELECT_HOME, vec![KeyChord::new(KeyCode::Home).shift()]);
+        bindings.bind(TEXT_SELECT_END, vec![KeyChord::new(KeyCode::End).shift()]);
+        bindings.bind(TEXT_SELECT_UP, vec![KeyChord::new(KeyCode::Up).shift()]);
+        bindings.bind(TEXT_SELECT_DOWN, vec![KeyChord::new(KeyCode::Down).shift()]);
+        bindings.bind(
+            TEXT_NEWLINE,
+            vec![
+                KeyChord::new(KeyCode::Return),
+                KeyChord::new(KeyCode::NumpadEnter),
+                KeyChord::new(KeyCode::Return).shift(),
+            ],
+        );
+        bindings.bind(
+            TEXT_SELECT_WORD_LEFT,
+            vec![KeyChord::new(KeyCode::Left).ctrl().shift()],
+        );
+        bindings.bind(
+            TEXT_SELECT_WORD_RIGHT,
+            vec![KeyChord::new(KeyCode::Right).ctrl().shift()],
+        );
+        bindings.bind(TEXT_SELECT_ALL, vec![KeyChord::new(KeyCode::A).ctrl()]);
+        bindings.bind(
+            TEXT_COPY,
+            vec![
+                KeyChord::new(KeyCode::C).ctrl(),
+                KeyChord::new(KeyCode::Insert).ctrl(),
+            ],
+        );
+        bindings.bind(
+            TEXT_CUT,
+            vec![
+                KeyChord::new(KeyCode::X).ctrl(),
+                KeyChord::new(KeyCode::Delete).shift(),
+            ],
+        );
+        bindings.bind(
+            TEXT_PASTE,
+            vec![
+                KeyChord::new(KeyCode::V).ctrl(),
+                KeyChord::new(KeyCode::Insert).shift(),
+            ],
+        );
+        bindings.bind(TEXT_UNDO, vec![KeyChord::new(KeyCode::Z).ctrl()]);
+        bindings.bind(
+            TEXT_REDO,
+            vec![
+                KeyChord::new(KeyCode::Z).ctrl().shift(),
+                KeyChord::new(KeyCode::Y).ctrl(),
+            ],
+        );
+        bindings.bind(LIST_PAGE_UP, vec![KeyChord::new(KeyCode::PageUp)]);
+        bindings.bind(LIST_PAGE_DOWN, vec![KeyChord::new(KeyCode::PageDown)]);
+        bindings.bind(
+            SUGGESTION_NEXT,
+            vec![KeyChord::new(KeyCode::Down), KeyChord::new(KeyCode::Tab)],
+        );
+        bindings.bind(
+            SUGGESTION_PREVIOUS,
+            vec![
+                KeyChord::new(KeyCode::Up),
+                KeyChord::new(KeyCode::Tab).shift(),
+            ],
+        );
+        bindings.bind(
+            SUGGESTION_ACCEPT,
+            vec![
+                KeyChord::new(KeyCode::Return),
+                KeyChord::new(KeyCode::NumpadEnter),
+            ],
+        );
+        bindings.bind(SUGGESTION_DISMISS, vec![KeyChord::new(KeyCode::Escape)]);
+        bindings.bind(
+            BUTTON_ACTIVATE,
+            vec![
+                KeyChord::new(KeyCode::Return),
+                KeyChord::new(KeyCode::NumpadEnter),
+            ],
+        );
+
+        bindings
+    }
+}
+
+impl WidgetKeyBindings {
+    pub fn bind(&mut self, action: impl Into<String>, chords: Vec<KeyChord>) {
+        self.bindings.insert(action.into(), chords);
+    }
+
+    // Binds the action only if it isn't bound yet, keeping bindings loaded from a file.
+    pub fn bind_default(&mut self, action: impl Into<String>, chords: Vec<KeyChord>) {
+        self.bindings.entry(action.into()).or_insert(chords);
+    }
+
+    pub fn chords(&self, action: &str) -> &[KeyChord] {
+        self.bindings.get(action).map_or(&[], |chords| &chords[..])
+    }
+
+    pub fn just_pressed(&self, action: &str, input: &Input<KeyCode>) -> bool {
+        self.chords(action)
+            .iter()
+            .any(|chord| chord.just_pressed(input))
+    }
+
+    pub fn pressed(&self, action: &str, input: &Input<KeyCode>) -> bool {
+        self.chords(action).iter().any(|chord| chord.pressed(input))
+    }
+
+    // Like `just_pressed`, but the pressed keys are cleared so systems running later on the
+    // frame don't handle them again.
+    pub fn consume(&self, action: &str, input: &mut Input<KeyCode>) -> bool {
+        let pressed = self
+            .chords(action)
+            .iter()
+            .filter(|chord| chord.just_pressed(input))
+            .map(|chord| chord.key)
+            .collect::<Vec<_>>();
+
+        for &key in &pressed {
+            input.clear_just_pressed(key);
+        }
+
+        pressed.is_empty() == false
+    }
+
+    // Keys of the just pressed chords which also type a character, so text fields can skip
+    // the character each of them produced.
+    pub fn text_shortcut_keys(&self, input: &Input<KeyCode>) -> Vec<KeyCode> {
+        let mut keys = self
+            .bindings
+            .values()
+            .flatten()
+            .filter(|chord| chord.types_text() && chord.just_pressed(input))
+            .map(|chord| chord.key)
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys.dedup();
+        keys
+    }
+
+    // Actions missing on the file keep their default bindings.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let loaded: Self =
+            ron::from_str(&content).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))?;
+
+        let mut bindings = Self::default();
+        bindings.bindings.extend(loaded.bindings);
+        Ok(bindings)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let content = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| io::Error::new(ErrorKind::InvalidData, err))?;
+
+        fs::write(path, content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(keys: &[KeyCode]) -> Input<KeyCode> {
+        let mut input = Input::default();
+        for &key in keys {
+            input.press(key);
+        }
+        input
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("{name}_{}.ron", std::process::id()))
+    }
+
+    #[test]
+    fn chord_requires_exact_modifiers() {
+        let chord = KeyChord::new(KeyCode::Z).ctrl();
+
+        assert!(chord.just_pressed(&press(&[KeyCode::LControl, KeyCode::Z])));
+        assert!(chord.just_pressed(&press(&[KeyCode::RControl, KeyCode::Z])));
+        assert_eq!(chord.just_pressed(&press(&[KeyCode::Z])), false);
+        assert_eq!(
+            chord.just_pressed(&press(&[KeyCode::LControl, KeyCode::LShift, KeyCode::Z])),
+            false
+        );
+    }
+
+    #[test]
+    fn chord_displays_modifiers_first() {
+        let chord = KeyChord::new(KeyCode::Z).shift().ctrl();
+        assert_eq!(chord.to_string(), "Ctrl+Shift+Z");
+    }
+
+    #[test]
+    fn chord_parses_with_default_modifiers() {
+        let chord: KeyChord = ron::from_str("(key: A, alt: true)").unwrap();
+        assert_eq!(chord, KeyChord::new(KeyCode::A).alt());
+    }
+
+    #[test]
+    fn only_unmodified_editing_keys_dont_type() {
+        assert!(KeyChord::new(KeyCode::A).types_text());
+        assert!(KeyChord::new(KeyCode::Left).ctrl().types_text());
+        assert_eq!(KeyChord::new(KeyCode::Left).types_text(), false);
+        assert_eq!(KeyChord::new(KeyCode::Return).types_text(), false);
+    }
+
+    #[test]
+    fn key_code_for_char_ignores_case() {
+        assert_eq!(key_code_for_char('s'), Some(KeyCode::S));
+        assert_eq!(key_code_for_char('S'), Some(KeyCode::S));
+        assert_eq!(key_code_for_char('7'), Some(KeyCode::Key7));
+        assert_eq!(key_code_for_char('é'), None);
+    }
+
+    #[test]
+    fn bind_default_keeps_existing_binding() {
+        let mut bindings = WidgetKeyBindings::default();
+        bindings.bind_default(TEXT_UNDO, vec![KeyChord::new(KeyCode::U)]);
+        bindings.bind_default("custom", vec![KeyChord::new(KeyCode::C)]);
+
+        assert_eq!(
+            bindings.chords(TEXT_UNDO),
+            &[KeyChord::new(KeyCode::Z).ctrl()]
+        );
+        assert_eq!(bindings.chords("custom"), &[KeyChord::new(KeyCode::C)]);
+    }
+
+    #[test]
+    fn consume_clears_pressed_keys() {
+        let bindings = WidgetKeyBindings::default();
+        let mut input = press(&[KeyCode::PageUp]);
+
+        assert!(bindings.consume(LIST_PAGE_UP, &mut input));
+        assert_eq!(bindings.just_pressed(LIST_PAGE_UP, &input), false);
+    }
+
+    #[test]
+    fn text_shortcut_keys_lists_typing_chords() {
+        let bindings = WidgetKeyBindings::default();
+
+        let input = press(&[KeyCode::LControl, KeyCode::A]);
+        assert_eq!(bindings.text_shortcut_keys(&input), vec![KeyCode::A]);
+
+        // Arrows are editing keys, they never type a character.
+        let input = press(&[KeyCode::Left]);
+        assert!(bindings.text_shortcut_keys(&input).is_empty());
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let path = temp_path("key_bindings_round_trip");
+        let mut bindings = WidgetKeyBindings::default();
+        bindings.bind(TEXT_SUBMIT, vec![KeyChord::new(KeyCode::U).alt()]);
+        bindings.bind("custom", vec![]);
+
+        bindings.save(&path).unwrap();
+        let loaded = WidgetKeyBindings::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.bindings, bindings.bindings);
+    }
+
+    #[test]
+    fn load_keeps_defaults_for_missing_actions() {
+        let path = temp_path("key_bindings_partial");
+        fs::write(&path, "(bindings: {\"text.submit\": [(key: U)]})").unwrap();
+
+        let loaded = WidgetKeyBindings::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.chords(TEXT_SUBMIT), &[KeyChord::new(KeyCode::U)]);
+        assert_eq!(
+            loaded.chords(TEXT_DELETE_BACK),
+            WidgetKeyBindings::default().chords(TEXT_DELETE_BACK)
+        );
+    }
+
+    #[test]
+    fn load_reports_invalid_files() {
+        let path = temp_path("key_bindings_invalid");
+        fs::write(&path, "not bindings").unwrap();
+
+        let err = WidgetKeyBindings::load(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+}
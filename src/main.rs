@@ -4,11 +4,21 @@ use button::{ButtonClicked, TextButton};
 use console::{CommandIssued, Console, ConsoleAction};
 use widget::{Widget, WidgetEventReader, WidgetLabel, WidgetPlugin};
 
+mod accessibility;
+mod autocomplete;
 mod button;
+mod clipboard;
 mod console;
 // mod focus;
 mod input_text;
 mod item_list;
+mod key_bindings;
+mod nine_slice;
+mod number_input;
+mod pointer;
+mod text_area;
+mod theme;
+mod tooltip;
 mod widget;
 
 #[derive(Component)]
@@ -0,0 +1,254 @@
+use bevy::{prelude::*, ui::FocusPolicy};
+
+use crate::widget::{screen_to_layout, WidgetFrames};
+
+pub(super) struct NineSlicePlugin;
+
+impl Plugin for NineSlicePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(build_nine_slice)
+            .add_system(remove_nine_slice);
+    }
+}
+
+// Replaces the colored frame of a widget by an image, keeping the corners at their original
+// size and stretching the edges and the center. Margins are in pixels of the source image.
+#[derive(Component, Clone, PartialEq)]
+pub struct NineSlice {
+    pub image: Handle<Image>,
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
+}
+
+impl NineSlice {
+    pub fn new(image: Handle<Image>, margin: f32) -> Self {
+        Self {
+            image,
+            left: margin,
+            right: margin,
+            top: margin,
+            bottom: margin,
+        }
+    }
+
+    pub fn with_margins(mut self, left: f32, right: f32, top: f32, bottom: f32) -> Self {
+        self.left = left;
+        self.right = right;
+        self.top = top;
+        self.bottom = bottom;
+        self
+    }
+}
+
+#[derive(Component)]
+struct NineSliceMeta {
+    background: Entity,
+    // Slice the background was built from, a new one replaces it once its image is loaded.
+    built: NineSlice,
+    // Colors of the frames before they were hidden, restored when `NineSlice` is removed.
+    frame_colors: Vec<(Entity, Color)>,
+}
+
+#[derive(Component)]
+struct NineSliceBackground;
+
+#[derive(Clone, Copy)]
+enum Slice {
+    Start,
+    Middle,
+    End,
+}
+
+impl Slice {
+    // Returns the cell length, the image length and the image offset along a single axis.
+    fn layout(self, length: f32, start: f32, end: f32) -> (Val, Val, Val) {
+        let center = (length - start - end).max(1.0);
+
+        match self {
+            Slice::Start => (Val::Px(start), Val::Px(length), Val::Px(0.0)),
+            Slice::Middle => (
+                Val::Auto,
+                Val::Percent(100.0 * length / center),
+                Val::Percent(-100.0 * start / center),
+            ),
+            Slice::End => (Val::Px(end), Val::Px(length), Val::Px(end - length)),
+        }
+    }
+
+    fn grow(self) -> f32 {
+        match self {
+            Slice::Middle => 1.0,
+            _ => 0.0,
+        }
+    }
+}
+
+const SLICES: [Slice; 3] = [Slice::Start, Slice::Middle, Slice::End];
+
+fn spawn_cell(
+    commands: &mut Commands,
+    slice: &NineSlice,
+    image_size: Vec2,
+    column: Slice,
+    row: Slice,
+) -> Entity {
+    let (cell_width, image_width, left) = column.layout(image_size.x, slice.left, slice.right);
+    let (_, image_height, top) = row.layout(image_size.y, slice.top, slice.bottom);
+
+    let image = commands
+        .spawn_bundle(ImageBundle {
+            style: Style {
+                size: Size::new(image_width, image_height),
+                position_type: PositionType::Absolute,
+                position: screen_to_layout(UiRect::new(left, Val::Undefined, top, Val::Undefined)),
+                ..default()
+            },
+            image: slice.image.clone().into(),
+            focus_policy: FocusPolicy::Pass,
+            ..default()
+        })
+        .id();
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(cell_width, Val::Percent(100.0)),
+                flex_grow: column.grow(),
+                flex_shrink: 0.0,
+                overflow: Overflow::Hidden,
+                ..default()
+            },
+            focus_policy: FocusPolicy::Pass,
+            color: Color::NONE.into(),
+            ..default()
+        })
+        .add_child(image)
+        .id()
+}
+
+fn spawn_background(commands: &mut Commands, slice: &NineSlice, image_size: Vec2) -> Entity {
+    let rows = SLICES
+        .iter()
+        .map(|&row| {
+            let (row_height, ..) = row.layout(image_size.y, slice.top, slice.bottom);
+            let cells = SLICES
+                .iter()
+                .map(|&column| spawn_cell(commands, slice, image_size, column, row))
+                .collect::<Vec<_>>();
+
+            commands
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(100.0), row_height),
+                        flex_grow: row.grow(),
+                        flex_shrink: 0.0,
+                        flex_direction: FlexDirection::Row,
+                        ..default()
+                    },
+                    focus_policy: FocusPolicy::Pass,
+                    color: Color::NONE.into(),
+                    ..default()
+                })
+                .push_children(&cells)
+                .id()
+        })
+        .collect::<Vec<_>>();
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                position: UiRect::new(Val::Px(0.0), Val::Undefined, Val::Px(0.0), Val::Undefined),
+                // Flex column grows from the bottom, so reverse it to keep the top row on top.
+                flex_direction: FlexDirection::ColumnReverse,
+                ..default()
+            },
+            focus_policy: FocusPolicy::Pass,
+            color: Color::NONE.into(),
+            ..default()
+        })
+        .insert(NineSliceBackground)
+        .insert(Name::new("Nine Slice"))
+        .push_children(&rows)
+        .id()
+}
+
+fn build_nine_slice(
+    mut commands: Commands,
+    mut q: Query<(
+        Entity,
+        &NineSlice,
+        Option<&WidgetFrames>,
+        Option<&mut NineSliceMeta>,
+    )>,
+    mut q_color: Query<&mut UiColor>,
+    images: Res<Assets<Image>>,
+) {
+    for (e, slice, frames, meta) in &mut q {
+        if meta.as_ref().map_or(false, |meta| meta.built == *slice) {
+            continue;
+        }
+
+        // Wait until the image is loaded, since its size is needed to compute the slices. The
+        // previous background is kept until then.
+        let image_size = match images.get(&slice.image) {
+            Some(image) => image.size(),
+            None => continue,
+        };
+
+        let background = spawn_background(&mut commands, slice, image_size);
+        commands.entity(e).insert_children(0, &[background]);
+
+        match meta {
+            Some(mut meta) => {
+                commands.entity(meta.background).despawn_recursive();
+                meta.background = background;
+                meta.built = slice.clone();
+            }
+            None => {
+                let frame_colors = frames
+                    .into_iter()
+                    .flat_map(|frames| frames.0.iter())
+                    .filter_map(|&frame| {
+                        let mut color = q_color.get_mut(frame).ok()?;
+                        let original = color.0;
+                        color.0 = Color::NONE;
+                        Some((frame, original))
+                    })
+                    .collect();
+
+                commands.entity(e).insert(NineSliceMeta {
+                    background,
+                    built: slice.clone(),
+                    frame_colors,
+                });
+            }
+        }
+    }
+}
+
+fn remove_nine_slice(
+    mut commands: Commands,
+    removed: RemovedComponents<NineSlice>,
+    q_meta: Query<&NineSliceMeta>,
+    mut q_color: Query<&mut UiColor>,
+) {
+    for e in removed.iter() {
+        let meta = match q_meta.get(e) {
+            Ok(meta) => meta,
+            Err(_) => continue,
+        };
+
+        commands.entity(meta.background).despawn_recursive();
+        for &(frame, original) in &meta.frame_colors {
+            if let Ok(mut color) = q_color.get_mut(frame) {
+                color.0 = original;
+            }
+        }
+
+        commands.entity(e).remove::<NineSliceMeta>();
+    }
+}
@@ -0,0 +1,628 @@
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+    ui::FocusPolicy,
+};
+use bevy_ui_navigation::prelude::{FocusState, Focusable};
+
+use crate::{
+    accessibility::{AccessibilityNode, AccessibilityRole},
+    button::{ButtonClicked, ButtonTrigger, TextButton},
+    input_text::{InputFilter, InputText, InputTextPointer, InputValidator, TextSubmitted},
+    pointer::{cursor_position, PointerDown, PointerTarget},
+    widget::{Disabled, StringLabel, Widget, WidgetEvent, WidgetLabel},
+};
+
+const FIELD_HEIGHT: f32 = 20.0;
+// Cursor travel needed before a drag on the field starts scrubbing, and for each step after.
+const SCRUB_THRESHOLD: f32 = 4.0;
+const SCRUB_STEP_WIDTH: f32 = 8.0;
+// Wheel travel for each step, touchpads scroll by pixels and mice by lines of this height.
+const WHEEL_STEP_HEIGHT: f32 = 20.0;
+
+pub(super) struct NumberInputPlugin;
+
+impl Plugin for NumberInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<NumberInput>()
+            .add_event::<ValueChanged<f64>>()
+            .add_system(update_number_text)
+            .add_system(update_number_filter)
+            .add_system(commit_number_text)
+            .add_system(step_on_click)
+            .add_system(step_on_wheel)
+            .add_system(
+                scrub_number
+                    .after(InputTextPointer)
+                    .before(commit_number_text),
+            );
+    }
+}
+
+// Numeric field with increment and decrement buttons. Typed text is parsed when submitted or
+// when the field loses focus. Insert a new one after building the widget to configure it.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct NumberInput {
+    pub min: f64,
+    pub max: f64,
+    pub step: f64,
+    // Decimal places the value is rounded to.
+    pub precision: usize,
+    value: f64,
+}
+
+impl Default for NumberInput {
+    fn default() -> Self {
+        Self {
+            min: f64::MIN,
+            max: f64::MAX,
+            step: 1.0,
+            precision: 0,
+            value: 0.0,
+        }
+    }
+}
+
+impl NumberInput {
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.min = min;
+        self.max = max;
+        self.value = self.constrain(self.value);
+        self
+    }
+
+    pub fn with_step(mut self, step: f64) -> Self {
+        self.step = step;
+        self
+    }
+
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self.value = self.constrain(self.value);
+        self
+    }
+
+    pub fn with_value(mut self, value: f64) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    // Clamps and rounds the value, returns `false` when it didn't change.
+    pub fn set_value(&mut self, value: f64) -> bool {
+        let value = self.constrain(value);
+        if value == self.value {
+            return false;
+        }
+
+        self.value = value;
+        true
+    }
+
+    pub fn format(&self) -> String {
+        format!("{:.*}", self.precision, self.value)
+    }
+
+    fn constrain(&self, value: f64) -> f64 {
+        let scale = 10f64.powi(self.precision as i32);
+        ((value * scale).round() / scale).clamp(self.min, self.max)
+    }
+
+    // Only lets the sign and the decimal point be typed when the configuration allows them.
+    fn filter(&self) -> InputFilter {
+        let negative = self.min < 0.0;
+        let decimal = self.precision > 0;
+
+        InputFilter::default().with_predicate(move |c| {
+            c.is_ascii_digit() || (negative && c == '-') || (decimal && c == '.')
+        })
+    }
+
+    // Partial numbers are valid while typing, malformed ones are fixed when committed.
+    fn validator(&self) -> InputValidator {
+        let sign = if self.min < 0.0 { "-?" } else { "" };
+        let decimals = if self.precision > 0 {
+            format!(r"(\.\d{{0,{}}})?", self.precision)
+        } else {
+            String::new()
+        };
+
+        InputValidator::pattern(&format!(r"{sign}\d*{decimals}"))
+            .expect("Number pattern should be valid")
+    }
+}
+
+// Sent when the value is changed through the widget, not when it's set by code.
+pub struct ValueChanged<T> {
+    pub entity: Entity,
+    pub value: T,
+}
+
+impl<T: Send + Sync + 'static> WidgetEvent for ValueChanged<T> {
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+}
+
+#[derive(Component)]
+struct NumberInputMeta {
+    text_entity: Entity,
+    // Whether the field was focused on the last frame, to commit the text when it loses focus.
+    editing: bool,
+    scrub: Option<ScrubState>,
+    // Wheel travel over the field not turned into steps yet.
+    wheel: f32,
+    // Whether negative values are allowed and the precision, when the filter was made.
+    filter_config: Option<(bool, usize)>,
+}
+
+struct ScrubState {
+    start_x: f32,
+    start_value: f64,
+    active: bool,
+}
+
+#[derive(Component)]
+struct SpinButton {
+    input: Entity,
+    direction: f64,
+}
+
+impl Widget for NumberInput {
+    fn build<L: WidgetLabel>(
+        label: L,
+        commands: &mut Commands,
+        asset_server: &AssetServer,
+    ) -> Entity {
+        let name = label.name();
+
+        let text = InputText::build(
+            StringLabel::from(format!("{name} Text").as_str()),
+            commands,
+            asset_server,
+        );
+
+        // The filter and validator follow the configuration, see `update_number_filter`.
+        commands.entity(text).insert(Style {
+            size: Size::new(Val::Auto, Val::Px(FIELD_HEIGHT)),
+            border: UiRect::all(Val::Px(2.0)),
+            flex_grow: 1.0,
+            ..default()
+        });
+
+        let mut input = commands.spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Px(FIELD_HEIGHT)),
+                align_items: AlignItems::Center,
+                flex_shrink: 0.0,
+                ..default()
+            },
+            focus_policy: FocusPolicy::Pass,
+            color: Color::NONE.into(),
+            ..default()
+        });
+        let input_entity = input.id();
+
+        input
+            .add_child(text)
+            .insert(Name::new(name.clone()))
+            .insert(AccessibilityNode::new(AccessibilityRole::SpinButton).with_name(name))
+            .insert(label)
+            .insert(NumberInput::default())
+            .insert(NumberInputMeta {
+                text_entity: text,
+                editing: false,
+                scrub: None,
+                wheel: 0.0,
+                filter_config: None,
+            });
+
+        for (button_label, direction) in [("-", -1.0), ("+", 1.0)] {
+            let button = TextButton::build(StringLabel::from(button_label), commands, asset_server);
+
+            commands
+                .entity(button)
+                .insert(Style {
+                    size: Size::new(Val::Px(FIELD_HEIGHT), Val::Px(FIELD_HEIGHT)),
+                    flex_shrink: 0.0,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                })
+                // Holding the button keeps stepping.
+                .insert(ButtonTrigger::Repeat {
+                    delay: 0.4,
+                    interval: 0.05,
+                })
+                .insert(SpinButton {
+                    input: input_entity,
+                    direction,
+                });
+
+            commands.entity(input_entity).add_child(button);
+        }
+
+        input_entity
+    }
+}
+
+// Shows the value on the field whenever it's changed by code or by the configuration.
+fn update_number_text(
+    mut q: Query<(&NumberInput, &NumberInputMeta, &mut AccessibilityNode), Changed<NumberInput>>,
+    mut q_text: Query<&mut InputText>,
+) {
+    for (number, meta, mut node) in &mut q {
+        let text = number.format();
+
+        if let Ok(mut input_text) = q_text.get_mut(meta.text_entity) {
+            if input_text.text() != text {
+                input_text.set_text(text.clone());
+            }
+        }
+
+        node.value = Some(text);
+    }
+}
+
+fn update_number_filter(
+    mut commands: Commands,
+    mut q: Query<(&NumberInput, &mut NumberInputMeta), Changed<NumberInput>>,
+) {
+    for (number, mut meta) in &mut q {
+        // The value changes far more often than what can be typed.
+        let config = (number.min < 0.0, number.precision);
+        if meta.filter_config == Some(config) {
+            continue;
+        }
+        meta.filter_config = Some(config);
+
+        commands
+            .entity(meta.text_entity)
+            .insert(number.filter())
+            .insert(number.validator());
+    }
+}
+
+fn commit_number_text(
+    mut q: Query<(Entity, &mut NumberInput, &mut NumberInputMeta), Without<Disabled>>,
+    mut q_text: Query<(&mut InputText, &Focusable)>,
+    mut submitted: EventReader<TextSubmitted>,
+    mut writer: EventWriter<ValueChanged<f64>>,
+) {
+    let submitted = submitted.iter().map(|evt| evt.entity).collect::<Vec<_>>();
+
+    for (entity, mut number, mut meta) in &mut q {
+        let (mut input_text, focus) = match q_text.get_mut(meta.text_entity) {
+            Ok(text) => text,
+            Err(_) => continue,
+        };
+
+        let focused = focus.state() == FocusState::Focused;
+        let blurred = meta.editing && focused == false;
+        if meta.editing != focused {
+            meta.editing = focused;
+        }
+
+        if blurred == false && submitted.contains(&meta.text_entity) == false {
+            continue;
+        }
+
+        // Unparsable text goes back to the current value.
+        if let Ok(value) = input_text.text().trim().parse::<f64>() {
+            if number.set_value(value) {
+                writer.send(ValueChanged {
+                    entity,
+                    value: number.value(),
+                });
+            }
+        }
+
+        let text = number.format();
+        if input_text.text() != text {
+            input_text.set_text(text);
+        }
+    }
+}
+
+fn step_on_click(
+    mut q: Query<&mut NumberInput, Without<Disabled>>,
+    q_buttons: Query<&SpinButton>,
+    mut clicked: EventReader<ButtonClicked>,
+    mut writer: EventWriter<ValueChanged<f64>>,
+) {
+    for &ButtonClicked(button) in clicked.iter() {
+        let spin = match q_buttons.get(button) {
+            Ok(spin) => spin,
+            Err(_) => continue,
+        };
+
+        if let Ok(mut number) = q.get_mut(spin.input) {
+            let value = number.value() + number.step * spin.direction;
+
+            if number.set_value(value) {
+                writer.send(ValueChanged {
+                    entity: spin.input,
+                    value: number.value(),
+                });
+            }
+        }
+    }
+}
+
+fn step_on_wheel(
+    mut q: Query<(Entity, &mut NumberInput, &mut NumberInputMeta), Without<Disabled>>,
+    q_targets: Query<&PointerTarget>,
+    mut wheel: EventReader<MouseWheel>,
+    mut writer: EventWriter<ValueChanged<f64>>,
+) {
+    let travel = wheel
+        .iter()
+        .map(|evt| match evt.unit {
+            MouseScrollUnit::Line => evt.y * WHEEL_STEP_HEIGHT,
+            MouseScrollUnit::Pixel => evt.y,
+        })
+        .sum::<f32>();
+
+    for (entity, mut number, mut meta) in &mut q {
+        let hovered = q_targets
+            .get(meta.text_entity)
+            .map_or(false, |target| target.is_hovered());
+
+        // Travel is only kept while the field stays hovered.
+        if hovered == false {
+            if meta.wheel != 0.0 {
+                meta.wheel = 0.0;
+            }
+            continue;
+        }
+
+        if travel == 0.0 {
+            continue;
+        }
+
+        meta.wheel += travel;
+        let steps = (meta.wheel / WHEEL_STEP_HEIGHT).trunc();
+        meta.wheel -= steps * WHEEL_STEP_HEIGHT;
+
+        if steps != 0.0 {
+            let value = number.value() + number.step * steps as f64;
+
+            if number.set_value(value) {
+                writer.send(ValueChanged {
+                    entity,
+                    value: number.value(),
+                });
+            }
+        }
+    }
+}
+
+// Dragging the field sideways changes the value by a step every few pixels. Only drags which
+// start while the field isn't being edited scrub, so text can still be selected once focused.
+// Once scrubbing, the selection made by the drag is dropped.
+fn scrub_number(
+    mut q: Query<(Entity, &mut NumberInput, &mut NumberInputMeta), Without<Disabled>>,
+    mut q_text: Query<(&mut InputText, &PointerTarget)>,
+    windows: Res<Windows>,
+    mut down: EventReader<PointerDown>,
+    mut writer: EventWriter<ValueChanged<f64>>,
+) {
+    let cursor = match cursor_position(&windows) {
+        Some(cursor) => cursor,
+        None => return,
+    };
+
+    let pressed = down
+        .iter()
+        .filter(|evt| evt.button == MouseButton::Left)
+        .map(|evt| evt.entity)
+        .collect::<Vec<_>>();
+
+    for (entity, mut number, mut meta) in &mut q {
+        let (mut input_text, target) = match q_text.get_mut(meta.text_entity) {
+            Ok(text) => text,
+            Err(_) => continue,
+        };
+
+        // Runs before `commit_number_text`, so `editing` is still from before this press.
+        if pressed.contains(&meta.text_entity) && meta.editing == false {
+            meta.scrub = Some(ScrubState {
+                start_x: cursor.x,
+                start_value: number.value(),
+                active: false,
+            });
+        }
+
+        if target.is_pressed() == false {
+            if meta.scrub.is_some() {
+                meta.scrub = None;
+            }
+            continue;
+        }
+
+        let scrub = match &mut meta.scrub {
+            Some(scrub) => scrub,
+            None => continue,
+        };
+
+        let distance = cursor.x - scrub.start_x;
+        if scrub.active == false && distance.abs() < SCRUB_THRESHOLD {
+            continue;
+        }
+        scrub.active = true;
+
+        let steps = (distance / SCRUB_STEP_WIDTH).trunc() as f64;
+        let value = scrub.start_value + number.step * steps;
+
+        if number.set_value(value) {
+            writer.send(ValueChanged {
+                entity,
+                value: number.value(),
+            });
+        }
+
+        if input_text.selection().is_some() {
+            let end = input_text.text().len();
+            input_text.set_caret(end);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        asset::AssetPlugin,
+        core::CorePlugin,
+        ecs::{event::Events, system::CommandQueue},
+        input::InputPlugin,
+        text::DefaultTextPipeline,
+    };
+    use bevy_ui_navigation::{prelude::NavRequest, DefaultNavigationPlugins};
+
+    use super::*;
+    use crate::{
+        button::ButtonPlugin, key_bindings::WidgetKeyBindings, pointer::PointerPlugin,
+        theme::WidgetTheme,
+    };
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_plugin(InputPlugin)
+            .add_plugins(DefaultNavigationPlugins)
+            .add_asset::<Font>()
+            .add_event::<TextSubmitted>()
+            .init_resource::<Time>()
+            .init_resource::<Windows>()
+            .init_resource::<DefaultTextPipeline>()
+            .init_resource::<WidgetTheme>()
+            .init_resource::<WidgetKeyBindings>()
+            .add_plugin(PointerPlugin)
+            .add_plugin(ButtonPlugin)
+            .add_plugin(NumberInputPlugin);
+        app
+    }
+
+    #[test]
+    fn spin_button_click_steps_once() {
+        let mut app = app();
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &app.world);
+        let input = NumberInput::build(StringLabel::from("Number"), &mut commands, &asset_server);
+        queue.apply(&mut app.world);
+
+        let increment = app
+            .world
+            .query::<(Entity, &SpinButton)>()
+            .iter(&app.world)
+            .find(|(_, spin)| spin.direction > 0.0)
+            .map(|(e, _)| e)
+            .unwrap();
+        // There is no layout, so give the button a size for the pointer to be over it.
+        app.world.get_mut::<Node>(increment).unwrap().size = Vec2::splat(FIELD_HEIGHT);
+        app.world
+            .resource_mut::<Events<NavRequest>>()
+            .send(NavRequest::FocusOn(increment));
+        app.update();
+
+        // Navigation also requests the focused button's action when the pointer is released.
+        *app.world.get_mut::<Interaction>(increment).unwrap() = Interaction::Clicked;
+        app.update();
+        *app.world.get_mut::<Interaction>(increment).unwrap() = Interaction::Hovered;
+        app.world
+            .resource_mut::<Events<NavRequest>>()
+            .send(NavRequest::Action);
+        app.update();
+        app.update();
+
+        assert_eq!(app.world.get::<NumberInput>(input).unwrap().value(), 1.0);
+    }
+
+    #[test]
+    fn constrain_rounds_to_precision() {
+        let number = NumberInput::default().with_precision(2);
+
+        assert_eq!(number.constrain(1.234), 1.23);
+        assert_eq!(number.constrain(1.235001), 1.24);
+        assert_eq!(number.constrain(-0.004), -0.0);
+    }
+
+    #[test]
+    fn constrain_clamps_to_range() {
+        let number = NumberInput::default().with_range(0.0, 10.0);
+
+        assert_eq!(number.constrain(-5.0), 0.0);
+        assert_eq!(number.constrain(12.0), 10.0);
+        assert_eq!(number.constrain(7.4), 7.0);
+    }
+
+    #[test]
+    fn range_constrains_current_value() {
+        let number = NumberInput::default()
+            .with_value(50.0)
+            .with_range(0.0, 10.0);
+
+        assert_eq!(number.value(), 10.0);
+    }
+
+    #[test]
+    fn set_value_reports_changes() {
+        let mut number = NumberInput::default().with_range(0.0, 10.0);
+
+        assert!(number.set_value(3.0));
+        assert_eq!(number.set_value(3.2), false);
+        assert_eq!(number.set_value(-1.0), true);
+        assert_eq!(number.value(), 0.0);
+    }
+
+    #[test]
+    fn format_pads_to_precision() {
+        let number = NumberInput::default().with_precision(2).with_value(1.5);
+        assert_eq!(number.format(), "1.50");
+
+        let number = NumberInput::default().with_value(-3.0);
+        assert_eq!(number.format(), "-3");
+    }
+
+    #[test]
+    fn filter_follows_configuration() {
+        let input = InputText::default();
+
+        let integer = NumberInput::default().with_range(0.0, 100.0);
+        assert_eq!(integer.filter().filter_insert(&input, "-1.5a"), "15");
+
+        let decimal = NumberInput::default()
+            .with_range(-1.0, 1.0)
+            .with_precision(1);
+        assert_eq!(decimal.filter().filter_insert(&input, "-1.5a"), "-1.5");
+    }
+
+    #[test]
+    fn validator_accepts_partial_numbers() {
+        let number = NumberInput::default().with_precision(2);
+        let validator = number.validator();
+
+        assert!((validator.0)(""));
+        assert!((validator.0)("-"));
+        assert!((validator.0)("-1."));
+        assert!((validator.0)("1.25"));
+        assert_eq!((validator.0)("1.255"), false);
+        assert_eq!((validator.0)("1-"), false);
+    }
+
+    #[test]
+    fn validator_rejects_sign_when_not_negative() {
+        let number = NumberInput::default().with_range(0.0, 10.0);
+        let validator = number.validator();
+
+        assert!((validator.0)("5"));
+        assert_eq!((validator.0)("-5"), false);
+        assert_eq!((validator.0)("5.0"), false);
+    }
+}
@@ -0,0 +1,214 @@
+use bevy::{ecs::system::SystemParam, prelude::*, ui::UiSystem};
+
+use crate::widget::{Disabled, WidgetEvent};
+
+const DOUBLE_CLICK_TIME: f64 = 0.3;
+
+pub(super) struct PointerPlugin;
+
+impl Plugin for PointerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PointerEnter>()
+            .add_event::<PointerLeave>()
+            .add_event::<PointerDown>()
+            .add_event::<PointerUp>()
+            .add_event::<Click>()
+            .add_event::<DoubleClick>()
+            .add_event::<RightClick>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                dispatch_pointer_events.after(UiSystem::Focus),
+            )
+            .add_system_to_stage(CoreStage::PreUpdate, release_disabled_targets);
+    }
+}
+
+// Any entity with an `Interaction` and a `PointerTarget` will receive pointer events.
+#[derive(Component, Default)]
+pub struct PointerTarget {
+    hovered: bool,
+    pressed: bool,
+    right_pressed: bool,
+    last_click: Option<f64>,
+}
+
+impl PointerTarget {
+    pub fn is_hovered(&self) -> bool {
+        self.hovered
+    }
+
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+}
+
+macro_rules! pointer_event {
+    ($name:ident) => {
+        pub struct $name {
+            pub entity: Entity,
+            // Cursor position relative to the top-left corner of the widget.
+            pub position: Vec2,
+        }
+
+        impl WidgetEvent for $name {
+            fn entity(&self) -> Entity {
+                self.entity
+            }
+        }
+    };
+    ($name:ident, button) => {
+        pub struct $name {
+            pub entity: Entity,
+            pub position: Vec2,
+            pub button: MouseButton,
+        }
+
+        impl WidgetEvent for $name {
+            fn entity(&self) -> Entity {
+                self.entity
+            }
+        }
+    };
+}
+
+pointer_event!(PointerEnter);
+pointer_event!(PointerLeave);
+pointer_event!(PointerDown, button);
+pointer_event!(PointerUp, button);
+pointer_event!(Click);
+pointer_event!(DoubleClick);
+pointer_event!(RightClick);
+
+pub(crate) fn cursor_position(windows: &Windows) -> Option<Vec2> {
+    windows
+        .get_primary()
+        .and_then(|window| window.cursor_position())
+}
+
+// UI coordinates grow upwards from the bottom-left corner of the window, so flip them to make
+// the position grow downwards from the top-left corner of the node, like the layout does.
+pub(crate) fn relative_cursor_position(
+    cursor: Vec2,
+    node: &Node,
+    transform: &GlobalTransform,
+) -> Vec2 {
+    let center = transform.translation().truncate();
+    let top_left = center + Vec2::new(-node.size.x, node.size.y) / 2.0;
+    Vec2::new(cursor.x - top_left.x, top_left.y - cursor.y)
+}
+
+#[derive(SystemParam)]
+struct PointerWriters<'w, 's> {
+    enter: EventWriter<'w, 's, PointerEnter>,
+    leave: EventWriter<'w, 's, PointerLeave>,
+    down: EventWriter<'w, 's, PointerDown>,
+    up: EventWriter<'w, 's, PointerUp>,
+    click: EventWriter<'w, 's, Click>,
+    double_click: EventWriter<'w, 's, DoubleClick>,
+    right_click: EventWriter<'w, 's, RightClick>,
+}
+
+fn dispatch_pointer_events(
+    mut q: Query<
+        (
+            Entity,
+            &Interaction,
+            &Node,
+            &GlobalTransform,
+            &mut PointerTarget,
+        ),
+        Without<Disabled>,
+    >,
+    windows: Res<Windows>,
+    mouse: Res<Input<MouseButton>>,
+    time: Res<Time>,
+    mut writers: PointerWriters,
+) {
+    let cursor = cursor_position(&windows);
+
+    for (entity, interaction, node, transform, mut target) in &mut q {
+        let position = cursor
+            .map(|cursor| relative_cursor_position(cursor, node, transform))
+            .unwrap_or_default();
+
+        // `Interaction::Clicked` is kept while the button is held, even when the cursor leaves.
+        let hovered = match interaction {
+            Interaction::Hovered => true,
+            Interaction::Clicked => {
+                position.cmpge(Vec2::ZERO).all() && position.cmplt(node.size).all()
+            }
+            Interaction::None => false,
+        };
+
+        if hovered != target.hovered {
+            target.hovered = hovered;
+
+            if hovered {
+                writers.enter.send(PointerEnter { entity, position });
+            } else {
+                writers.leave.send(PointerLeave { entity, position });
+            }
+        }
+
+        if *interaction == Interaction::Clicked && target.pressed == false {
+            target.pressed = true;
+            writers.down.send(PointerDown {
+                entity,
+                position,
+                button: MouseButton::Left,
+            });
+        } else if target.pressed && *interaction != Interaction::Clicked {
+            target.pressed = false;
+            writers.up.send(PointerUp {
+                entity,
+                position,
+                button: MouseButton::Left,
+            });
+
+            if hovered {
+                writers.click.send(Click { entity, position });
+
+                let now = time.seconds_since_startup();
+                match target.last_click {
+                    Some(last) if now - last <= DOUBLE_CLICK_TIME => {
+                        target.last_click = None;
+                        writers.double_click.send(DoubleClick { entity, position });
+                    }
+                    _ => target.last_click = Some(now),
+                }
+            }
+        }
+
+        // `Interaction` only tracks the left button, so right button is checked by hand.
+        if hovered && mouse.just_pressed(MouseButton::Right) {
+            target.right_pressed = true;
+            writers.down.send(PointerDown {
+                entity,
+                position,
+                button: MouseButton::Right,
+            });
+        } else if target.right_pressed && mouse.pressed(MouseButton::Right) == false {
+            target.right_pressed = false;
+            writers.up.send(PointerUp {
+                entity,
+                position,
+                button: MouseButton::Right,
+            });
+
+            // Like clicks, only when pressed and released over the same target.
+            if hovered {
+                writers.right_click.send(RightClick { entity, position });
+            }
+        }
+    }
+}
+
+// Disabled targets get no events, so a press in progress would otherwise end with a stale
+// `PointerUp` and click once they are enabled again.
+fn release_disabled_targets(mut q: Query<&mut PointerTarget, Added<Disabled>>) {
+    for mut target in &mut q {
+        target.pressed = false;
+        target.right_pressed = false;
+        target.last_click = None;
+    }
+}
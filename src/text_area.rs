@@ -0,0 +1,804 @@
+use std::iter;
+
+use ab_glyph::{Font as _, PxScale, ScaleFont};
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+    text::{DefaultTextPipeline, TextLayoutInfo},
+    ui::FocusPolicy,
+};
+use bevy_ui_navigation::prelude::{FocusState, Focusable};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    accessibility::{AccessibilityNode, AccessibilityRole},
+    input_text::{InputFilter, InputText, InputTextCaret, InputTextDisplayCaret, InputTextEditing},
+    key_bindings::{WidgetKeyBindings, TEXT_NEWLINE},
+    pointer::{cursor_position, DoubleClick, PointerDown, PointerTarget},
+    theme::WidgetTheme,
+    widget::{screen_to_layout, Disabled, Widget, WidgetFrames, WidgetLabel},
+};
+
+const FONT_SIZE: f32 = 15.0;
+const PADDING: f32 = 4.0;
+const BORDER: f32 = 2.0;
+
+#[derive(SystemLabel)]
+struct UpdateLayout;
+
+pub(super) struct TextAreaPlugin;
+
+impl Plugin for TextAreaPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<TextArea>()
+            .add_system(update_font_metrics.before(UpdateLayout))
+            .add_system(update_text_area_layout.label(UpdateLayout))
+            .add_system(insert_newline)
+            .add_system(update_text_area_size.after(UpdateLayout))
+            .add_system(update_text_area_sections.after(UpdateLayout))
+            .add_system(resolve_line_moves.after(UpdateLayout))
+            .add_system(update_text_area_pointer.after(UpdateLayout))
+            .add_system(place_text_area_caret.after(UpdateLayout))
+            .add_system(scroll_text_area.after(UpdateLayout))
+            .add_system(update_line_numbers.after(UpdateLayout));
+    }
+}
+
+// Multi-line `InputText`, wrapping words to its width. Insert a new one after building the
+// widget to change the rows or show line numbers.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct TextArea {
+    // Visible rows, the widget height is fitted to them.
+    pub rows: usize,
+    pub line_numbers: bool,
+}
+
+impl Default for TextArea {
+    fn default() -> Self {
+        Self {
+            rows: 5,
+            line_numbers: false,
+        }
+    }
+}
+
+impl TextArea {
+    pub fn with_rows(mut self, rows: usize) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    pub fn with_line_numbers(mut self) -> Self {
+        self.line_numbers = true;
+        self
+    }
+}
+
+#[derive(Component)]
+struct TextAreaMeta {
+    // Clipped area the text is scrolled within.
+    viewport_entity: Entity,
+    // Holds the line numbers, the text and the caret.
+    scroll_entity: Entity,
+    text_entity: Entity,
+    numbers_entity: Entity,
+    font: Handle<Font>,
+    // Unknown until the font is loaded.
+    metrics: Option<FontMetrics>,
+    // Glyphs from the last time the text was laid out.
+    layout: Option<TextAreaLayout>,
+    // How far the text is scrolled down, in pixels.
+    scroll: f32,
+    // Set on edits, so the scroll follows the caret once the layout catches up.
+    follow_caret: bool,
+}
+
+#[derive(Component)]
+struct TextAreaText;
+
+#[derive(Component)]
+struct TextAreaScroll;
+
+// Advances of the font used by the text, in logical pixels.
+#[derive(Clone, Copy, PartialEq)]
+struct FontMetrics {
+    line_height: f32,
+    // Spaces and line breaks have no glyph, so the caret is moved over them by this much.
+    space_width: f32,
+}
+
+impl FontMetrics {
+    fn new(font: &Font, font_size: f32) -> Self {
+        let font = font.font.as_scaled(PxScale::from(font_size));
+        Self {
+            line_height: font.height() + font.line_gap(),
+            space_width: font.h_advance(font.glyph_id(' ')),
+        }
+    }
+}
+
+struct GlyphBox {
+    // Byte range of the glyph on the text.
+    index: usize,
+    end: usize,
+    left: f32,
+    right: f32,
+    line: usize,
+}
+
+// Glyphs of the laid out text, in logical pixels from the top-left corner of the text node.
+struct TextAreaLayout {
+    glyphs: Vec<GlyphBox>,
+    metrics: FontMetrics,
+}
+
+impl TextAreaLayout {
+    // The glyphs were laid out from the sections of `text`, which may be behind the
+    // `InputText` for a frame.
+    fn new(info: &TextLayoutInfo, text: &Text, metrics: FontMetrics, scale_factor: f32) -> Self {
+        let section_offsets = text
+            .sections
+            .iter()
+            .scan(0, |offset, section| {
+                let start = *offset;
+                *offset += section.value.len();
+                Some(start)
+            })
+            .collect::<Vec<_>>();
+        let value = text
+            .sections
+            .iter()
+            .map(|section| section.value.as_str())
+            .collect::<String>();
+
+        // Glyph positions grow upwards from the bottom of the text block, while lines are
+        // counted from its top.
+        let block_height = info.size.y / scale_factor;
+
+        let mut glyphs = info
+            .glyphs
+            .iter()
+            .filter_map(|glyph| {
+                let index = section_offsets.get(glyph.section_index)? + glyph.byte_index;
+                let c = value.get(index..)?.chars().next()?;
+                let x = glyph.position.x / scale_factor;
+                let half_width = glyph.size.x / scale_factor / 2.0;
+                let from_top = block_height - glyph.position.y / scale_factor;
+
+                Some(GlyphBox {
+                    index,
+                    end: index + c.len_utf8(),
+                    left: x - half_width,
+                    right: x + half_width,
+                    line: (from_top / metrics.line_height).max(0.0) as usize,
+                })
+            })
+            .collect::<Vec<_>>();
+        glyphs.sort_by_key(|glyph| glyph.index);
+
+        Self { glyphs, metrics }
+    }
+
+    // Horizontal position and line of the caret when placed at `index`.
+    fn caret_location(&self, text: &str, index: usize) -> (f32, usize) {
+        let next = self.glyphs.partition_point(|glyph| glyph.index < index);
+        if let Some(glyph) = self.glyphs.get(next).filter(|glyph| glyph.index == index) {
+            return (glyph.left, glyph.line);
+        }
+
+        let (x, line, from) = match next.checked_sub(1).map(|i| &self.glyphs[i]) {
+            Some(prev) => (prev.right, prev.line, prev.end.min(index)),
+            None => (0.0, 0, 0),
+        };
+
+        let space_width = self.metrics.space_width;
+        let gap = text.get(from..index).unwrap_or_default();
+        match gap.rfind('\n') {
+            Some(i) => (
+                gap[i + 1..].chars().count() as f32 * space_width,
+                line + gap.matches('\n').count(),
+            ),
+            None => (x + gap.chars().count() as f32 * space_width, line),
+        }
+    }
+
+    // Caret index closest to `x` on `line`, or on the nearest line when it's empty.
+    fn caret_at(&self, text: &str, x: f32, line: usize) -> usize {
+        text.grapheme_indices(true)
+            .map(|(i, _)| i)
+            .chain(iter::once(text.len()))
+            .map(|index| {
+                let (caret_x, caret_line) = self.caret_location(text, index);
+                (index, caret_line.abs_diff(line), (caret_x - x).abs())
+            })
+            .min_by(|a, b| (a.1, a.2).partial_cmp(&(b.1, b.2)).unwrap())
+            .map_or(0, |(index, ..)| index)
+    }
+
+    fn line_count(&self, text: &str) -> usize {
+        self.caret_location(text, text.len()).1 + 1
+    }
+}
+
+impl Widget for TextArea {
+    fn build<L: WidgetLabel>(
+        label: L,
+        commands: &mut Commands,
+        asset_server: &AssetServer,
+    ) -> Entity {
+        let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+        let text_style = TextStyle {
+            font: font.clone(),
+            font_size: FONT_SIZE,
+            color: WidgetTheme::default().text,
+        };
+
+        let numbers = commands
+            .spawn_bundle(
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        color: WidgetTheme::default().placeholder_text,
+                        ..text_style.clone()
+                    },
+                )
+                .with_style(Style {
+                    display: Display::None,
+                    margin: UiRect::new(
+                        Val::Undefined,
+                        Val::Px(PADDING),
+                        Val::Undefined,
+                        Val::Undefined,
+                    ),
+                    flex_shrink: 0.0,
+                    ..default()
+                }),
+            )
+            .id();
+
+        // Sections hold the text before, inside and after the selection.
+        let text = commands
+            .spawn_bundle(TextBundle::from_sections([
+                TextSection::new("", text_style.clone()),
+                TextSection::new("", text_style.clone()),
+                TextSection::new("", text_style.clone()),
+            ]))
+            .insert(TextAreaText)
+            .id();
+
+        let caret = commands
+            .spawn_bundle(
+                TextBundle::from_section(
+                    "|",
+                    TextStyle {
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                        ..text_style
+                    },
+                )
+                .with_style(Style {
+                    display: Display::None,
+                    position_type: PositionType::Absolute,
+                    ..default()
+                }),
+            )
+            .insert(Focusable::default())
+            .insert(InputTextDisplayCaret)
+            .id();
+
+        // Aligned to the end, since the cross axis is flipped on screen too.
+        let scroll = commands
+            .spawn_bundle(NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Auto),
+                    align_items: AlignItems::FlexEnd,
+                    flex_shrink: 0.0,
+                    position: UiRect::new(
+                        Val::Undefined,
+                        Val::Undefined,
+                        Val::Px(0.0),
+                        Val::Undefined,
+                    ),
+                    ..default()
+                },
+                focus_policy: FocusPolicy::Pass,
+                color: Color::NONE.into(),
+                ..default()
+            })
+            .add_child(numbers)
+            .add_child(text)
+            .add_child(caret)
+            .insert(TextAreaScroll)
+            .id();
+
+        let viewport = commands
+            .spawn_bundle(NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    padding: UiRect::all(Val::Px(PADDING)),
+                    align_items: AlignItems::FlexEnd,
+                    overflow: Overflow::Hidden,
+                    ..default()
+                },
+                focus_policy: FocusPolicy::Pass,
+                color: Color::rgba(0.1, 0.1, 0.1, 0.9).into(),
+                ..default()
+            })
+            .add_child(scroll)
+            .id();
+
+        let text_area = TextArea::default();
+        // Fitted to the rows once the font is loaded.
+        let height = panel_height(text_area.rows, FONT_SIZE);
+
+        let mut panel = commands.spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Px(height)),
+                border: UiRect::all(Val::Px(BORDER)),
+                flex_shrink: 0.0,
+                ..default()
+            },
+            focus_policy: FocusPolicy::Block,
+            color: Color::rgba(0.5, 0.5, 0.5, 0.1).into(),
+            ..default()
+        });
+        let panel_entity = panel.id();
+
+        panel
+            .add_child(viewport)
+            .insert(WidgetFrames(vec![panel_entity, viewport]))
+            .insert(Name::new(label.name()))
+            .insert(AccessibilityNode::new(AccessibilityRole::TextBox).with_name(label.name()))
+            .insert(label)
+            .insert(Focusable::new().blocked())
+            .insert(Interaction::default())
+            .insert(PointerTarget::default())
+            .insert(InputText::new_multiline())
+            .insert(InputTextEditing::default())
+            .insert(text_area)
+            .insert(TextAreaMeta {
+                viewport_entity: viewport,
+                scroll_entity: scroll,
+                text_entity: text,
+                numbers_entity: numbers,
+                font,
+                metrics: None,
+                layout: None,
+                scroll: 0.0,
+                follow_caret: false,
+            })
+            .insert(InputTextCaret::new(caret))
+            .id()
+    }
+}
+
+fn panel_height(rows: usize, line_height: f32) -> f32 {
+    rows as f32 * line_height + (PADDING + BORDER) * 2.0
+}
+
+fn update_font_metrics(mut q: Query<&mut TextAreaMeta>, fonts: Res<Assets<Font>>) {
+    for mut meta in &mut q {
+        let metrics = fonts
+            .get(&meta.font)
+            .map(|font| FontMetrics::new(font, FONT_SIZE));
+
+        if meta.metrics != metrics {
+            meta.metrics = metrics;
+        }
+    }
+}
+
+// Text is laid out after the frame, so glyphs only match the text on the next one.
+fn update_text_area_layout(
+    mut q: Query<(&mut TextAreaMeta, ChangeTrackers<InputText>), With<TextArea>>,
+    q_text: Query<(&Text, ChangeTrackers<Text>, ChangeTrackers<Node>), With<TextAreaText>>,
+    pipeline: Res<DefaultTextPipeline>,
+    windows: Res<Windows>,
+) {
+    let scale_factor = windows
+        .get_primary()
+        .map_or(1.0, |window| window.scale_factor() as f32);
+
+    for (mut meta, input_tracker) in &mut q {
+        let (text, text_tracker, node_tracker) = match q_text.get(meta.text_entity) {
+            Ok(text) => text,
+            Err(_) => continue,
+        };
+        let metrics = match meta.metrics {
+            Some(metrics) => metrics,
+            None => continue,
+        };
+
+        let outdated = meta.layout.as_ref().map_or(true, |layout| {
+            layout.metrics != metrics
+                || input_tracker.is_changed()
+                || text_tracker.is_changed()
+                || node_tracker.is_changed()
+        });
+        if outdated == false {
+            continue;
+        }
+
+        let layout = pipeline
+            .get_glyphs(&meta.text_entity)
+            .map(|info| TextAreaLayout::new(info, text, metrics, scale_factor));
+        meta.layout = layout;
+    }
+}
+
+fn insert_newline(
+    mut q: Query<
+        (&Focusable, &mut InputText, Option<&InputFilter>),
+        (With<TextArea>, Without<Disabled>),
+    >,
+    input_keycode: Res<Input<KeyCode>>,
+    bindings: Res<WidgetKeyBindings>,
+) {
+    if bindings.just_pressed(TEXT_NEWLINE, &input_keycode) == false {
+        return;
+    }
+
+    for (focus, mut input_text, filter) in &mut q {
+        if focus.state() != FocusState::Focused {
+            continue;
+        }
+
+        // Newlines count towards `max_length` like any other typed character.
+        let accepted = match filter {
+            Some(filter) => filter.filter_insert(&input_text, "\n"),
+            None => "\n".to_string(),
+        };
+        if accepted.is_empty() == false {
+            input_text.insert('\n');
+        }
+    }
+}
+
+fn update_text_area_size(
+    mut q: Query<(&TextArea, &TextAreaMeta, &mut Style)>,
+    q_nodes: Query<&Node>,
+    mut q_children: Query<&mut Style, Without<TextArea>>,
+) {
+    for (text_area, meta, mut style) in &mut q {
+        let metrics = match meta.metrics {
+            Some(metrics) => metrics,
+            None => continue,
+        };
+
+        let height = Val::Px(panel_height(text_area.rows, metrics.line_height));
+        if style.size.height != height {
+            style.size.height = height;
+        }
+
+        let numbers_display = if text_area.line_numbers {
+            Display::Flex
+        } else {
+            Display::None
+        };
+
+        let numbers_width = match q_children.get_mut(meta.numbers_entity) {
+            Ok(mut numbers_style) => {
+                if numbers_style.display != numbers_display {
+                    numbers_style.display = numbers_display;
+                }
+
+                match q_nodes.get(meta.numbers_entity) {
+                    Ok(node) if text_area.line_numbers => node.size.x + PADDING,
+                    _ => 0.0,
+                }
+            }
+            Err(_) => 0.0,
+        };
+
+        // Text only wraps when its maximum width is set in pixels.
+        if let Ok(viewport) = q_nodes.get(meta.viewport_entity) {
+            // Room is kept for the caret after a trailing space.
+            let space_width = metrics.space_width;
+            let wrap_width = Val::Px(
+                (viewport.size.x - PADDING * 2.0 - numbers_width - space_width).max(space_width),
+            );
+
+            if let Ok(mut text_style) = q_children.get_mut(meta.text_entity) {
+                if text_style.max_size.width != wrap_width {
+                    text_style.max_size.width = wrap_width;
+                }
+            }
+        }
+    }
+}
+
+// Runs after the layout, which then sees the new text as changed on its next run, once the
+// text pipeline has laid it out.
+fn update_text_area_sections(
+    q: Query<
+        (
+            Entity,
+            &InputText,
+            &TextAreaMeta,
+            Option<&Disabled>,
+            ChangeTrackers<InputText>,
+        ),
+        With<TextArea>,
+    >,
+    q_added: Query<(), (With<TextArea>, Added<Disabled>)>,
+    removed: RemovedComponents<Disabled>,
+    mut q_text: Query<&mut Text, With<TextAreaText>>,
+    theme: Res<WidgetTheme>,
+) {
+    let removed = removed.iter().collect::<Vec<_>>();
+
+    for (entity, input_text, meta, disabled, input_tracker) in &q {
+        if input_tracker.is_changed() == false
+            && theme.is_changed() == false
+            && q_added.contains(entity) == false
+            && removed.contains(&entity) == false
+        {
+            continue;
+        }
+
+        let mut text = match q_text.get_mut(meta.text_entity) {
+            Ok(text) => text,
+            Err(_) => continue,
+        };
+
+        let range = input_text
+            .selection()
+            .unwrap_or(input_text.caret()..input_text.caret());
+        let color = if disabled.is_some() {
+            theme.disabled_text
+        } else {
+            theme.text
+        };
+
+        // Selected text is drawn with the selection color, since sections have no background.
+        for (section, value, color) in [
+            (0, &input_text.text()[..range.start], color),
+            (1, &input_text.text()[range.clone()], theme.text_selection),
+            (2, &input_text.text()[range.end..], color),
+        ] {
+            text.sections[section].value = value.to_string();
+            text.sections[section].style.color = color;
+        }
+    }
+}
+
+fn resolve_line_moves(mut q: Query<(&mut InputText, &TextAreaMeta), With<TextArea>>) {
+    for (mut input_text, meta) in &mut q {
+        if input_text.has_line_move() == false {
+            continue;
+        }
+
+        let (lines, select) = match input_text.take_line_move() {
+            Some(line_move) => line_move,
+            None => continue,
+        };
+
+        let layout = match &meta.layout {
+            Some(layout) => layout,
+            None => continue,
+        };
+
+        let text = input_text.text();
+        let (x, line) = layout.caret_location(text, input_text.caret());
+        let target = line as isize + lines;
+
+        // Moving past the first or last line goes to the start or the end of the text.
+        let caret = if target < 0 {
+            0
+        } else if target as usize >= layout.line_count(text) {
+            text.len()
+        } else {
+            layout.caret_at(text, x, target as usize)
+        };
+
+        if select {
+            input_text.select_to(caret);
+        } else {
+            input_text.set_caret(caret);
+        }
+    }
+}
+
+fn update_text_area_pointer(
+    mut q: Query<(&PointerTarget, &mut InputText, &TextAreaMeta), Without<Disabled>>,
+    q_text_nodes: Query<(&Node, &GlobalTransform), With<TextAreaText>>,
+    windows: Res<Windows>,
+    input_keycode: Res<Input<KeyCode>>,
+    mut down: EventReader<PointerDown>,
+    mut double_click: EventReader<DoubleClick>,
+) {
+    let cursor = match cursor_position(&windows) {
+        Some(cursor) => cursor,
+        None => return,
+    };
+
+    let caret_at = |input_text: &InputText, meta: &TextAreaMeta| {
+        let (node, transform) = q_text_nodes.get(meta.text_entity).ok()?;
+        let layout = meta.layout.as_ref()?;
+
+        let top_left =
+            transform.translation().truncate() + Vec2::new(-node.size.x, node.size.y) / 2.0;
+        let x = cursor.x - top_left.x;
+        let line = ((top_left.y - cursor.y) / layout.metrics.line_height).max(0.0) as usize;
+
+        Some(layout.caret_at(input_text.text(), x, line))
+    };
+
+    for evt in down.iter() {
+        if evt.button != MouseButton::Left {
+            continue;
+        }
+
+        if let Ok((_, mut input_text, meta)) = q.get_mut(evt.entity) {
+            if let Some(caret) = caret_at(&input_text, meta) {
+                if input_keycode.any_pressed([KeyCode::LShift, KeyCode::RShift]) {
+                    input_text.select_to(caret);
+                } else {
+                    input_text.set_caret(caret);
+                }
+            }
+        }
+    }
+
+    for evt in double_click.iter() {
+        if let Ok((_, mut input_text, meta)) = q.get_mut(evt.entity) {
+            if let Some(caret) = caret_at(&input_text, meta) {
+                input_text.select_word_at(caret);
+            }
+        }
+    }
+
+    // Dragging extends the selection from where the pointer went down.
+    for (target, mut input_text, meta) in &mut q {
+        if target.is_pressed() {
+            if let Some(caret) = caret_at(&input_text, meta) {
+                if caret != input_text.caret() {
+                    input_text.select_to(caret);
+                }
+            }
+        }
+    }
+}
+
+fn place_text_area_caret(
+    q: Query<(&InputText, &TextAreaMeta, &InputTextCaret), With<TextArea>>,
+    q_nodes: Query<(&Node, &GlobalTransform)>,
+    mut q_caret: Query<&mut Style, With<InputTextDisplayCaret>>,
+) {
+    for (input_text, meta, caret) in &q {
+        let layout = match &meta.layout {
+            Some(layout) => layout,
+            None => continue,
+        };
+        let (text_node, text_transform) = match q_nodes.get(meta.text_entity) {
+            Ok(text) => text,
+            Err(_) => continue,
+        };
+        let (scroll_node, scroll_transform) = match q_nodes.get(meta.scroll_entity) {
+            Ok(scroll) => scroll,
+            Err(_) => continue,
+        };
+        let mut style = match q_caret.get_mut(caret.entity()) {
+            Ok(style) => style,
+            Err(_) => continue,
+        };
+
+        let (x, line) = layout.caret_location(input_text.text(), input_text.caret());
+        let text_left = text_transform.translation().x - text_node.size.x / 2.0;
+        let scroll_left = scroll_transform.translation().x - scroll_node.size.x / 2.0;
+
+        let position = screen_to_layout(UiRect::new(
+            Val::Px(text_left - scroll_left + x),
+            Val::Undefined,
+            Val::Px(line as f32 * layout.metrics.line_height),
+            Val::Undefined,
+        ));
+
+        if style.position != position {
+            style.position = position;
+        }
+    }
+}
+
+fn scroll_text_area(
+    mut q: Query<(
+        &InputText,
+        &TextArea,
+        &mut TextAreaMeta,
+        &PointerTarget,
+        ChangeTrackers<InputText>,
+    )>,
+    mut q_scroll: Query<&mut Style, With<TextAreaScroll>>,
+    mut wheel: EventReader<MouseWheel>,
+) {
+    let wheel = wheel.iter().collect::<Vec<_>>();
+
+    for (input_text, text_area, mut meta, target, input_tracker) in &mut q {
+        let text = input_text.text();
+        let (caret_line, line_count, line_height) = match &meta.layout {
+            Some(layout) => (
+                layout.caret_location(text, input_text.caret()).1,
+                layout.line_count(text),
+                layout.metrics.line_height,
+            ),
+            None => continue,
+        };
+
+        let view_height = text_area.rows as f32 * line_height;
+        let mut scroll = meta.scroll;
+
+        if target.is_hovered() {
+            for evt in &wheel {
+                scroll -= match evt.unit {
+                    MouseScrollUnit::Line => evt.y * line_height,
+                    MouseScrollUnit::Pixel => evt.y,
+                };
+            }
+        }
+
+        // The layout of an edit is only known on the next frame, so follow the caret on both.
+        if input_tracker.is_changed() || meta.follow_caret {
+            meta.follow_caret = input_tracker.is_changed();
+
+            let caret_top = caret_line as f32 * line_height;
+            if caret_top < scroll {
+                scroll = caret_top;
+            } else if caret_top + line_height > scroll + view_height {
+                scroll = caret_top + line_height - view_height;
+            }
+        }
+
+        let max_scroll = (line_count as f32 * line_height - view_height).max(0.0);
+        scroll = scroll.clamp(0.0, max_scroll);
+
+        if scroll != meta.scroll {
+            meta.scroll = scroll;
+
+            // Moving the text up reveals the lines below.
+            if let Ok(mut style) = q_scroll.get_mut(meta.scroll_entity) {
+                style.position = screen_to_layout(UiRect::new(
+                    Val::Undefined,
+                    Val::Undefined,
+                    Val::Undefined,
+                    Val::Px(scroll),
+                ));
+            }
+        }
+    }
+}
+
+fn update_line_numbers(
+    q: Query<(&InputText, &TextArea, &TextAreaMeta)>,
+    mut q_text: Query<&mut Text, Without<TextAreaText>>,
+) {
+    for (input_text, text_area, meta) in &q {
+        if text_area.line_numbers == false {
+            continue;
+        }
+
+        let layout = match &meta.layout {
+            Some(layout) => layout,
+            None => continue,
+        };
+
+        // Wrapped lines are left without a number.
+        let text = input_text.text();
+        let mut numbers = vec![String::new(); layout.line_count(text)];
+        let line_starts = iter::once(0).chain(text.match_indices('\n').map(|(i, _)| i + 1));
+
+        for (number, start) in line_starts.enumerate() {
+            let line = layout.caret_location(text, start).1;
+            if let Some(slot) = numbers.get_mut(line) {
+                *slot = (number + 1).to_string();
+            }
+        }
+
+        let numbers = numbers.join("\n");
+
+        if let Ok(mut numbers_text) = q_text.get_mut(meta.numbers_entity) {
+            if numbers_text.sections[0].value != numbers {
+                numbers_text.sections[0].value = numbers;
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+
+// Colors applied by widget systems when a widget changes its state. Changing this resource at
+// runtime will restyle every widget on the next frame.
+pub struct WidgetTheme {
+    pub button_normal: Color,
+    pub button_hovered: Color,
+    pub button_clicked: Color,
+    pub button_checked: Color,
+    pub button_focused: Color,
+    pub focus_ring: Color,
+    pub disabled_mask: Color,
+    pub disabled_image_tint: Color,
+    pub text: Color,
+    pub disabled_text: Color,
+    pub text_selection: Color,
+    pub placeholder_text: Color,
+    pub invalid_text: Color,
+    pub highlighted_item: Color,
+}
+
+impl Default for WidgetTheme {
+    fn default() -> Self {
+        Self {
+            button_normal: Color::NONE,
+            button_hovered: Color::rgba(0.8, 0.8, 0.8, 0.3),
+            button_clicked: Color::rgba(0.05, 0.05, 0.05, 0.5),
+            button_checked: Color::rgba(0.3, 0.5, 0.8, 0.4),
+            button_focused: Color::rgba(0.8, 0.8, 0.8, 0.15),
+            focus_ring: Color::rgb(0.9, 0.7, 0.2),
+            disabled_mask: Color::rgba(0.2, 0.2, 0.2, 0.7),
+            disabled_image_tint: Color::rgb(0.4, 0.4, 0.4),
+            text: Color::rgb(0.7, 0.7, 0.7),
+            disabled_text: Color::rgb(0.4, 0.4, 0.4),
+            text_selection: Color::rgba(0.3, 0.5, 0.8, 0.6),
+            placeholder_text: Color::rgba(0.7, 0.7, 0.7, 0.4),
+            invalid_text: Color::rgb(0.9, 0.3, 0.3),
+            highlighted_item: Color::rgb(0.95, 0.95, 0.95),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use bevy::{prelude::*, ui::FocusPolicy};
+
+use crate::{
+    pointer::{PointerDown, PointerEnter, PointerLeave},
+    widget::screen_to_layout,
+};
+
+const TOOLTIP_DELAY: f32 = 0.5;
+const TOOLTIP_OFFSET: f32 = 24.0;
+
+pub(super) struct TooltipPlugin;
+
+impl Plugin for TooltipPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Tooltip>()
+            .init_resource::<TooltipState>()
+            .add_system(track_tooltip_target)
+            .add_system(show_tooltip);
+    }
+}
+
+// Text shown near the cursor after hovering a widget for a while.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Tooltip(pub String);
+
+struct TooltipState {
+    target: Option<Entity>,
+    timer: Timer,
+    node: Option<Entity>,
+}
+
+impl Default for TooltipState {
+    fn default() -> Self {
+        Self {
+            target: None,
+            timer: Timer::from_seconds(TOOLTIP_DELAY, false),
+            node: None,
+        }
+    }
+}
+
+impl TooltipState {
+    fn hide(&mut self, commands: &mut Commands) {
+        self.target = None;
+        if let Some(node) = self.node.take() {
+            commands.entity(node).despawn_recursive();
+        }
+    }
+}
+
+fn track_tooltip_target(
+    mut commands: Commands,
+    mut state: ResMut<TooltipState>,
+    mut enter: EventReader<PointerEnter>,
+    mut leave: EventReader<PointerLeave>,
+    mut down: EventReader<PointerDown>,
+    q: Query<(), With<Tooltip>>,
+) {
+    for evt in leave.iter() {
+        if state.target == Some(evt.entity) {
+            state.hide(&mut commands);
+        }
+    }
+
+    for evt in down.iter() {
+        if state.target == Some(evt.entity) {
+            state.hide(&mut commands);
+        }
+    }
+
+    for evt in enter.iter() {
+        if q.contains(evt.entity) {
+            state.hide(&mut commands);
+            state.target = Some(evt.entity);
+            state.timer.reset();
+        }
+    }
+}
+
+fn show_tooltip(
+    mut commands: Commands,
+    mut state: ResMut<TooltipState>,
+    q: Query<&Tooltip>,
+    windows: Res<Windows>,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+) {
+    let target = match state.target {
+        Some(target) if state.node.is_none() => target,
+        _ => return,
+    };
+
+    let tooltip = match q.get(target) {
+        Ok(tooltip) => tooltip,
+        Err(_) => {
+            state.hide(&mut commands);
+            return;
+        }
+    };
+
+    if state.timer.tick(time.delta()).just_finished() == false {
+        return;
+    }
+
+    let window = match windows.get_primary() {
+        Some(window) => window,
+        None => return,
+    };
+    let cursor = match window.cursor_position() {
+        Some(cursor) => cursor,
+        None => return,
+    };
+
+    let text = commands
+        .spawn_bundle(TextBundle::from_section(
+            tooltip.0.clone(),
+            TextStyle {
+                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                font_size: 13.0,
+                color: Color::rgb(0.9, 0.9, 0.9),
+            },
+        ))
+        .id();
+
+    let node = commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                // Right below the cursor. Its height isn't known yet, so the top edge is placed,
+                // which is measured from the top of the window unlike the cursor.
+                position: screen_to_layout(UiRect::new(
+                    Val::Px(cursor.x),
+                    Val::Undefined,
+                    Val::Px(window.height() - cursor.y + TOOLTIP_OFFSET),
+                    Val::Undefined,
+                )),
+                padding: UiRect::all(Val::Px(4.0)),
+                ..default()
+            },
+            focus_policy: FocusPolicy::Pass,
+            color: Color::rgba(0.05, 0.05, 0.05, 0.9).into(),
+            ..default()
+        })
+        .insert(Name::new("Tooltip"))
+        .add_child(text)
+        .id();
+
+    state.node = Some(node);
+}
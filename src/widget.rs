@@ -1,12 +1,30 @@
 use bevy::{
-    ecs::{event::Event, system::SystemParam},
+    ecs::{
+        event::Event,
+        system::{EntityCommands, SystemParam},
+    },
     prelude::*,
 };
-use bevy_ui_navigation::DefaultNavigationPlugins;
+use bevy_ui_navigation::{
+    prelude::{FocusState, Focusable, NavRequest},
+    DefaultNavigationPlugins,
+};
 
 use crate::{
-    button::ButtonPlugin, console::ConsolePlugin, input_text::InputTextPlugin,
+    accessibility::AccessibilityPlugin,
+    autocomplete::AutocompletePlugin,
+    button::ButtonPlugin,
+    clipboard::WidgetClipboard,
+    console::ConsolePlugin,
+    input_text::InputTextPlugin,
     item_list::ItemListPlugin,
+    key_bindings::{KeyRepeat, WidgetKeyBindings},
+    nine_slice::NineSlicePlugin,
+    number_input::NumberInputPlugin,
+    pointer::PointerPlugin,
+    text_area::TextAreaPlugin,
+    theme::WidgetTheme,
+    tooltip::TooltipPlugin,
 };
 
 pub struct WidgetPlugin;
@@ -18,7 +36,200 @@ impl Plugin for WidgetPlugin {
             .add_plugin(InputTextPlugin)
             .add_plugin(ConsolePlugin)
             .add_plugin(ButtonPlugin)
-            .register_type::<StringLabel>();
+            .add_plugin(AccessibilityPlugin)
+            .add_plugin(PointerPlugin)
+            .add_plugin(NineSlicePlugin)
+            .add_plugin(TooltipPlugin)
+            .add_plugin(TextAreaPlugin)
+            .add_plugin(AutocompletePlugin)
+            .add_plugin(NumberInputPlugin)
+            .init_resource::<WidgetTheme>()
+            .init_resource::<WidgetKeyBindings>()
+            .init_resource::<KeyRepeat>()
+            .init_resource::<WidgetClipboard>()
+            .register_type::<StringLabel>()
+            .register_type::<Disabled>()
+            .add_system(propagate_disabled)
+            .add_system(block_disabled_focus)
+            .add_system(unblock_enabled_focus);
+    }
+}
+
+// Bevy UI lays nodes out from the top-left corner but draws them from the bottom-left one, so
+// layout is flipped vertically on screen: `top` offsets and margins apply to the visual bottom
+// edge, and absolute positions are measured from the bottom of the window. Widgets describe
+// rects by their visual edges and convert them with this.
+pub(crate) fn screen_to_layout(rect: UiRect) -> UiRect {
+    UiRect {
+        top: rect.bottom,
+        bottom: rect.top,
+        ..rect
+    }
+}
+
+// Colored nodes which draws the widget frame, so they can be hidden when it's skinned.
+#[derive(Component)]
+pub(crate) struct WidgetFrames(pub Vec<Entity>);
+
+// Disabled widgets don't emit events, can't be focused and are greyed out. Descendants are
+// disabled along with the widget, so its parts don't react either.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Disabled;
+
+// Marks a `Disabled` which was copied from an ancestor, so only those are removed when the
+// ancestor is enabled again.
+#[derive(Component)]
+struct InheritedDisabled;
+
+pub trait WidgetEntityCommands {
+    fn set_disabled(&mut self, disabled: bool) -> &mut Self;
+}
+
+impl<'w, 's, 'a> WidgetEntityCommands for EntityCommands<'w, 's, 'a> {
+    // A widget disabled along with an ancestor is disabled on its own from now on, so it stays
+    // disabled once the ancestor is enabled again.
+    fn set_disabled(&mut self, disabled: bool) -> &mut Self {
+        if disabled {
+            self.insert(Disabled).remove::<InheritedDisabled>()
+        } else {
+            self.remove::<Disabled>()
+        }
+    }
+}
+
+fn propagate_disabled(
+    mut commands: Commands,
+    q_disabled: Query<Entity, (With<Disabled>, Or<(Added<Disabled>, Changed<Children>)>)>,
+    removed: RemovedComponents<Disabled>,
+    q_parent: Query<&Parent>,
+    q_children: Query<&Children>,
+    q_state: Query<(Option<&Disabled>, Option<&InheritedDisabled>)>,
+) {
+    fn disable(
+        entity: Entity,
+        commands: &mut Commands,
+        q_children: &Query<&Children>,
+        q_state: &Query<(Option<&Disabled>, Option<&InheritedDisabled>)>,
+    ) {
+        for &child in q_children
+            .get(entity)
+            .into_iter()
+            .flat_map(|children| children.iter())
+        {
+            if let Ok((None, _)) = q_state.get(child) {
+                commands
+                    .entity(child)
+                    .insert(Disabled)
+                    .insert(InheritedDisabled);
+            }
+            disable(child, commands, q_children, q_state);
+        }
+    }
+
+    fn enable(
+        entity: Entity,
+        commands: &mut Commands,
+        q_children: &Query<&Children>,
+        q_state: &Query<(Option<&Disabled>, Option<&InheritedDisabled>)>,
+    ) {
+        for &child in q_children
+            .get(entity)
+            .into_iter()
+            .flat_map(|children| children.iter())
+        {
+            match q_state.get(child) {
+                Ok((Some(_), Some(_))) => {
+                    commands
+                        .entity(child)
+                        .remove::<Disabled>()
+                        .remove::<InheritedDisabled>();
+                }
+                // Widgets disabled on their own keep their parts disabled.
+                Ok((Some(_), None)) => continue,
+                _ => (),
+            }
+            enable(child, commands, q_children, q_state);
+        }
+    }
+
+    for entity in &q_disabled {
+        disable(entity, &mut commands, &q_children, &q_state);
+    }
+
+    for entity in removed.iter() {
+        let inherited = match q_state.get(entity) {
+            Ok((None, inherited)) => inherited.is_some(),
+            _ => continue,
+        };
+
+        // Enabling a widget under a disabled ancestor only drops its own state.
+        let parent_disabled = q_parent.get(entity).map_or(false, |parent| {
+            matches!(q_state.get(parent.get()), Ok((Some(_), _)))
+        });
+        if parent_disabled {
+            commands
+                .entity(entity)
+                .insert(Disabled)
+                .insert(InheritedDisabled);
+            continue;
+        }
+
+        if inherited {
+            commands.entity(entity).remove::<InheritedDisabled>();
+        }
+        enable(entity, &mut commands, &q_children, &q_state);
+    }
+}
+
+// Blocking fails while the widget is focused, so focus the closest enabled widget first and
+// keep trying until the focus moved away.
+fn block_disabled_focus(
+    mut q: Query<(Entity, &mut Focusable, Option<&GlobalTransform>), With<Disabled>>,
+    q_enabled: Query<
+        (Entity, &Focusable, &GlobalTransform, &ComputedVisibility),
+        Without<Disabled>,
+    >,
+    mut writer: EventWriter<NavRequest>,
+) {
+    for (entity, mut focus, transform) in &mut q {
+        match focus.state() {
+            FocusState::Blocked => (),
+            FocusState::Focused => {
+                let position = transform.map_or(Vec3::ZERO, |t| t.translation());
+                let closest = q_enabled
+                    .iter()
+                    .filter(|(e, focus, _, visibility)| {
+                        *e != entity
+                            && visibility.is_visible()
+                            && focus.state() != FocusState::Blocked
+                    })
+                    .map(|(e, _, t, _)| (e, t.translation().distance_squared(position)))
+                    .min_by(|a, b| a.1.total_cmp(&b.1));
+
+                if let Some((closest, _)) = closest {
+                    writer.send(NavRequest::FocusOn(closest));
+                }
+            }
+            _ => {
+                focus.block();
+            }
+        }
+    }
+}
+
+fn unblock_enabled_focus(
+    removed: RemovedComponents<Disabled>,
+    mut q: Query<(&mut Focusable, Option<&ComputedVisibility>)>,
+) {
+    for e in removed.iter() {
+        if let Ok((mut focus, visibility)) = q.get_mut(e) {
+            // Hidden widgets stay blocked until they are visible again.
+            let visible = visibility.map_or(true, |v| v.is_visible());
+            if visible && focus.state() == FocusState::Blocked {
+                focus.unblock();
+            }
+        }
     }
 }
 
@@ -119,3 +330,86 @@ impl<'w, 's, E: WidgetEvent> WidgetEventReader<'w, 's, StringLabel, E> {
             .into_iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::CommandQueue;
+
+    use super::*;
+
+    fn app() -> (App, Entity, Entity) {
+        let mut app = App::new();
+        app.add_system(propagate_disabled);
+
+        let child = app.world.spawn().id();
+        let parent = app.world.spawn().push_children(&[child]).id();
+        (app, parent, child)
+    }
+
+    // Commands are applied once the frame ends, so an extra one lets them propagate.
+    fn set_disabled(app: &mut App, entity: Entity, disabled: bool) {
+        let mut queue = CommandQueue::default();
+        Commands::new(&mut queue, &app.world)
+            .entity(entity)
+            .set_disabled(disabled);
+        queue.apply(&mut app.world);
+
+        app.update();
+        app.update();
+    }
+
+    fn is_disabled(app: &App, entity: Entity) -> bool {
+        app.world.get::<Disabled>(entity).is_some()
+    }
+
+    #[test]
+    fn children_follow_their_parent() {
+        let (mut app, parent, child) = app();
+
+        set_disabled(&mut app, parent, true);
+        assert!(is_disabled(&app, child));
+
+        set_disabled(&mut app, parent, false);
+        assert_eq!(is_disabled(&app, child), false);
+    }
+
+    #[test]
+    fn child_disabled_on_purpose_stays_disabled() {
+        let (mut app, parent, child) = app();
+
+        set_disabled(&mut app, parent, true);
+        set_disabled(&mut app, child, true);
+        set_disabled(&mut app, parent, false);
+
+        assert!(is_disabled(&app, child));
+    }
+
+    #[test]
+    fn enabled_child_keeps_disabled_parent_state() {
+        let (mut app, parent, child) = app();
+
+        set_disabled(&mut app, parent, true);
+        set_disabled(&mut app, child, true);
+        set_disabled(&mut app, child, false);
+        assert!(is_disabled(&app, child));
+
+        set_disabled(&mut app, parent, false);
+        assert_eq!(is_disabled(&app, child), false);
+        assert!(app.world.get::<InheritedDisabled>(child).is_none());
+    }
+
+    #[test]
+    fn enabling_child_drops_inherited_state() {
+        let (mut app, parent, child) = app();
+
+        set_disabled(&mut app, parent, true);
+        // Enabled without going through `set_disabled`.
+        app.world.entity_mut(parent).remove::<Disabled>();
+        app.world.entity_mut(child).remove::<Disabled>();
+        app.update();
+        app.update();
+
+        assert_eq!(is_disabled(&app, child), false);
+        assert!(app.world.get::<InheritedDisabled>(child).is_none());
+    }
+}